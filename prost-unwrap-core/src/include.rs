@@ -1,24 +1,51 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::fs;
 use std::path::PathBuf;
 
 use derive_builder::Builder;
+use heck::ToUpperCamelCase;
+use proc_macro2::Delimiter;
+use proc_macro2::Group;
+use proc_macro2::Punct;
+use proc_macro2::Spacing;
 use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use proc_macro2::TokenTree;
 use proc_macro_error::abort;
 use proc_macro_error::abort_call_site;
+use quote::format_ident;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::Expr;
+use syn::ExprAssign;
+use syn::ExprCall;
+use syn::ExprCast;
+use syn::ExprField;
 use syn::ExprLit;
+use syn::ExprUnary;
 use syn::File;
 use syn::Ident;
+use syn::Item;
 use syn::Lit;
+use syn::Member;
 use syn::Path;
 use syn::Token;
+use syn::Type;
+use syn::TypePath;
+use syn::UnOp;
 
+use crate::validate_rules::ProtoRules;
+use crate::validate_rules::Rule;
+
+use self::spec_tree::FieldDefault;
+use self::spec_tree::FieldModifier;
+use self::spec_tree::FieldSkip;
+use self::spec_tree::FieldSkips;
 use self::spec_tree::SpecTree;
 use self::spec_tree::SpecTreeLeaf;
 
@@ -41,6 +68,78 @@ pub struct Config {
     pub items_suffix: Option<Ident>,
     pub source: SourceFile,
     pub spec_tree: SpecTree,
+    pub foreign_mods: Vec<ForeignMod>,
+    pub externs: Vec<Extern>,
+    #[builder(default = "None")]
+    pub timestamp_mapping: Option<TimeMapping>,
+    #[builder(default = "None")]
+    pub duration_mapping: Option<TimeMapping>,
+    #[builder(default = "None")]
+    pub json_mapping: Option<JsonMapping>,
+    pub anys: Vec<AnySpec>,
+    pub field_types: Vec<FieldTypeSpec>,
+    pub type_mappings: Vec<TypeMappingSpec>,
+    pub newtypes: Vec<NewtypeSpec>,
+    pub optionals: Vec<OptionalSpec>,
+    pub validate_rules: Vec<ProtoRules>,
+    pub validators: Vec<ValidatorSpec>,
+    /// Struct paths relative to the linked source module, specified with
+    /// `with_oneof_enum`
+    pub oneof_enums: Vec<Path>,
+    /// Struct paths relative to the linked source module, specified with
+    /// `with_transparent`
+    pub transparents: Vec<Path>,
+    /// Struct paths relative to the linked source module, specified with
+    /// `with_private_fields`
+    pub privates: Vec<Path>,
+    /// Struct paths relative to the linked source module, specified with
+    /// `with_builder`
+    pub builders: Vec<Path>,
+    pub nested_fields: Vec<NestedFieldsSpec>,
+    pub wildcards: Vec<WildcardSpec>,
+    pub renames: Vec<RenameSpec>,
+    pub flattens: Vec<FlattenSpec>,
+    #[builder(default = "None")]
+    pub default_policy: Option<DefaultPolicy>,
+}
+
+/// The policy for the structs not specified with `with_struct`, specified
+/// with `with_default_policy`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum DefaultPolicy {
+    /// Every optional message field is required, as with `[*]`
+    RequireMessages,
+}
+
+/// The type family the `prost_types::Timestamp` and `prost_types::Duration`
+/// fields are mapped into.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum TimeMapping {
+    /// `std::time::SystemTime` and `std::time::Duration`
+    Std,
+    /// `chrono::DateTime<chrono::Utc>` and `chrono::TimeDelta`
+    Chrono,
+    /// `time::OffsetDateTime` and `time::Duration`
+    Time,
+}
+
+/// The type family the `prost_types::Struct`, `prost_types::Value` and
+/// `prost_types::ListValue` fields are mapped into.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum JsonMapping {
+    /// `serde_json::Map<String, serde_json::Value>`, `serde_json::Value` and
+    /// `Vec<serde_json::Value>`
+    SerdeJson,
+}
+
+/// The mirror module of a package, that is referenced by the linked source
+/// code, but is mirrored by another `include!` call.
+#[derive(Clone, Debug)]
+pub(crate) struct ForeignMod {
+    /// Module path relative to `with_original_mod` argument, e.g. `root`
+    pub fqn: Path,
+    /// Absolute path of the module mirroring the `fqn` module
+    pub this_mod_path: Path,
 }
 
 impl Display for Config {
@@ -65,8 +164,28 @@ impl Parse for Config {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut config_builder = ConfigBuilder::default();
         config_builder.spec_tree(SpecTree::new());
-        let mut expr = input
-            .parse::<Expr>()
+        config_builder.foreign_mods(Vec::new());
+        config_builder.externs(Vec::new());
+        config_builder.anys(Vec::new());
+        config_builder.field_types(Vec::new());
+        config_builder.type_mappings(Vec::new());
+        config_builder.newtypes(Vec::new());
+        config_builder.optionals(Vec::new());
+        config_builder.validate_rules(Vec::new());
+        config_builder.validators(Vec::new());
+        config_builder.oneof_enums(Vec::new());
+        config_builder.transparents(Vec::new());
+        config_builder.privates(Vec::new());
+        config_builder.builders(Vec::new());
+        config_builder.nested_fields(Vec::new());
+        config_builder.wildcards(Vec::new());
+        config_builder.renames(Vec::new());
+        config_builder.flattens(Vec::new());
+        let tokens = Self::normalize_field_lists(
+            Self::normalize_extern_mappings(input.parse::<TokenStream>()?),
+            false,
+        );
+        let mut expr = syn::parse2::<Expr>(tokens)
             .map_err(|e| {
                 abort_call_site!(format!("Unexpected syntax: {}", e));
             })
@@ -74,7 +193,7 @@ impl Parse for Config {
 
         Self::parse_call_chain(&mut config_builder, &mut expr);
 
-        let config = config_builder
+        let mut config = config_builder
             .build()
             .map_err(|e| match e {
                 ConfigBuilderError::UninitializedField("this_mod_path") => abort_call_site!(
@@ -93,17 +212,183 @@ impl Parse for Config {
             })
             .unwrap();
 
+        // the rules are matched against the linked source code structs, so
+        // the proto package must be the one of the linked source code
+        for proto_rules in &config.validate_rules {
+            let source_package = path_to_strings(&config.source.fqn);
+            if proto_rules.package != source_package {
+                abort!(
+                    proto_rules.path_span,
+                    format!(
+                        "Proto package `{}` does not match the `{}` module `{}`",
+                        proto_rules.package.join("."),
+                        Self::QUASI_FN_SOURCE,
+                        source_package.join("::")
+                    )
+                );
+            }
+        }
+
+        // the renamed items must be the structs or enums of the linked source
+        // code, not conflicting with the other items
+        config.validate_renames();
+
+        // the custom types are matched against the linked source code items,
+        // so the misspelled structs and fields are not silently ignored
+        config.validate_custom_types();
+
+        // the wildcards and the default policy are matched against the linked
+        // source code structs, so they are expanded once the source code is
+        // loaded; the explicitly specified structs are not affected by the
+        // default policy
+        for wildcard in config.wildcards.clone() {
+            config.expand_wildcard(&wildcard);
+        }
+        if config.default_policy == Some(DefaultPolicy::RequireMessages) {
+            let items = config.source.ast.items.clone();
+            config.apply_default_policy(&mut Vec::new(), &items);
+        }
+
+        // the nested fields are matched against the linked source code
+        // structs, so they are expanded once the source code is loaded
+        for nested_fields in config.nested_fields.clone() {
+            config.expand_nested_fields(
+                &nested_fields.fqn,
+                nested_fields.view.as_ref(),
+                &nested_fields.paths,
+            );
+        }
+
         Ok(config)
     }
 }
 
 impl Config {
+    const QUASI_FN_ANY: &'static str = "with_any";
+    const QUASI_FN_BUILDER: &'static str = "with_builder";
+    const QUASI_FN_DEFAULT_POLICY: &'static str = "with_default_policy";
     const QUASI_FN_ENUM_SPEC: &'static str = "with_enum";
+    const QUASI_FN_DURATION_MAPPING: &'static str = "with_duration";
+    const QUASI_FN_EXTERN: &'static str = "with_extern";
+    const QUASI_FN_FIELD_TYPE: &'static str = "with_field_type";
+    const QUASI_FN_FLATTEN: &'static str = "with_flatten";
+    const QUASI_FN_FOREIGN_MOD: &'static str = "with_foreign_mod";
     const QUASI_FN_ITEMS_SUFFIX: &'static str = "with_suffix";
+    const QUASI_FN_JSON_MAPPING: &'static str = "with_json";
+    const QUASI_FN_NEWTYPE: &'static str = "with_newtype";
+    const QUASI_FN_ONEOF_ENUM: &'static str = "with_oneof_enum";
+    const QUASI_FN_OPTIONAL: &'static str = "with_optional";
+    const QUASI_FN_PRIVATE_FIELDS: &'static str = "with_private_fields";
+    const QUASI_FN_RENAME: &'static str = "with_rename";
     const QUASI_FN_ORIG_MOD_PATH: &'static str = "with_original_mod";
     const QUASI_FN_SOURCE: &'static str = "from_source";
     const QUASI_FN_STRUCT_SPEC: &'static str = "with_struct";
     const QUASI_FN_THIS_MOD_PATH: &'static str = "with_this_mod";
+    const QUASI_FN_TIMESTAMP_MAPPING: &'static str = "with_timestamp";
+    const QUASI_FN_TRANSPARENT: &'static str = "with_transparent";
+    const QUASI_FN_TYPE_MAPPING: &'static str = "with_type_mapping";
+    const QUASI_FN_VALIDATE_RULES: &'static str = "with_validate_rules";
+    const QUASI_FN_VALIDATOR: &'static str = "with_validator";
+
+    /// Normalizes the `with_extern` modules mappings, e.g.
+    /// `with_extern(::a::generated => ::a::unwrapped)`, which are not valid
+    /// expressions, into the assignments, e.g.
+    /// `with_extern(::a::generated = ::a::unwrapped)`.
+    fn normalize_extern_mappings(tokens: TokenStream) -> TokenStream {
+        let mut is_extern = false;
+        tokens
+            .into_iter()
+            .map(|token| {
+                let token = match token {
+                    TokenTree::Group(group)
+                        if is_extern && group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        let mut replaced =
+                            Group::new(group.delimiter(), Self::replace_fat_arrows(group.stream()));
+                        replaced.set_span(group.span());
+                        TokenTree::Group(replaced)
+                    }
+                    token => token,
+                };
+                is_extern =
+                    matches!(&token, TokenTree::Ident(ident) if ident == Self::QUASI_FN_EXTERN);
+                token
+            })
+            .collect()
+    }
+
+    /// Normalizes the `with_struct` fields lists, which are not valid
+    /// expressions, so the macro arguments may be parsed as one:
+    /// - the `*` wildcard, e.g. `[*, !field1]`, is replaced with `_`;
+    /// - the renames map, e.g. `rename = { field1 => name1 }`, is replaced with
+    ///   the array, e.g. `rename = [field1 = name1]`.
+    fn normalize_field_lists(tokens: TokenStream, in_brackets: bool) -> TokenStream {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        let is_separator = |token: Option<&TokenTree>| match token {
+            None => true,
+            Some(TokenTree::Punct(punct)) => punct.as_char() == ',',
+            Some(_) => false,
+        };
+
+        tokens
+            .iter()
+            .enumerate()
+            .map(|(i, token)| match token {
+                TokenTree::Group(group) => {
+                    let is_renames_map = group.delimiter() == Delimiter::Brace
+                        && i >= 2
+                        && matches!(&tokens[i - 2], TokenTree::Ident(ident) if ident == "rename")
+                        && matches!(&tokens[i - 1], TokenTree::Punct(punct) if punct.as_char() == '=');
+                    let mut replaced = if is_renames_map {
+                        Group::new(
+                            Delimiter::Bracket,
+                            Self::replace_fat_arrows(group.stream()),
+                        )
+                    } else {
+                        Group::new(
+                            group.delimiter(),
+                            Self::normalize_field_lists(
+                                group.stream(),
+                                group.delimiter() == Delimiter::Bracket,
+                            ),
+                        )
+                    };
+                    replaced.set_span(group.span());
+                    TokenTree::Group(replaced)
+                }
+                TokenTree::Punct(punct)
+                    if in_brackets
+                        && punct.as_char() == '*'
+                        && is_separator(i.checked_sub(1).and_then(|i| tokens.get(i)))
+                        && is_separator(tokens.get(i + 1)) =>
+                {
+                    TokenTree::Ident(proc_macro2::Ident::new("_", punct.span()))
+                }
+                token => token.clone(),
+            })
+            .collect()
+    }
+
+    /// Replaces the `=>` punctuation with `=`, e.g. `field1 => name1` with
+    /// `field1 = name1`.
+    fn replace_fat_arrows(tokens: TokenStream) -> TokenStream {
+        let mut replaced: Vec<TokenTree> = Vec::new();
+        for token in tokens {
+            match (&token, replaced.last()) {
+                (TokenTree::Punct(punct), Some(TokenTree::Punct(prev_punct)))
+                    if punct.as_char() == '>'
+                        && prev_punct.as_char() == '='
+                        && prev_punct.spacing() == Spacing::Joint =>
+                {
+                    let mut eq_punct = Punct::new('=', Spacing::Alone);
+                    eq_punct.set_span(prev_punct.span());
+                    *replaced.last_mut().unwrap() = TokenTree::Punct(eq_punct);
+                }
+                _ => replaced.push(token),
+            }
+        }
+        replaced.into_iter().collect()
+    }
 
     /// Entry point for parsing macro arguments expression.
     /// The arguments expression is a quasi chain of method calls with the
@@ -166,6 +451,47 @@ impl Config {
                 Self::parse_struct_spec(config_builder, expr_args, expr_span)
             }
             Self::QUASI_FN_ENUM_SPEC => Self::parse_enum_spec(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_FOREIGN_MOD => {
+                Self::parse_foreign_mod(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_EXTERN => Self::parse_extern(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_TIMESTAMP_MAPPING => {
+                Self::parse_timestamp_mapping(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_DURATION_MAPPING => {
+                Self::parse_duration_mapping(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_JSON_MAPPING => {
+                Self::parse_json_mapping(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_ANY => Self::parse_any(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_FIELD_TYPE => {
+                Self::parse_field_type(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_TYPE_MAPPING => {
+                Self::parse_type_mapping(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_NEWTYPE => Self::parse_newtype(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_OPTIONAL => Self::parse_optional(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_VALIDATE_RULES => {
+                Self::parse_validate_rules(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_VALIDATOR => Self::parse_validator(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_ONEOF_ENUM => {
+                Self::parse_oneof_enum(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_TRANSPARENT => {
+                Self::parse_transparent(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_DEFAULT_POLICY => {
+                Self::parse_default_policy(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_RENAME => Self::parse_rename(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_FLATTEN => Self::parse_flatten(config_builder, expr_args, expr_span),
+            Self::QUASI_FN_PRIVATE_FIELDS => {
+                Self::parse_private_fields(config_builder, expr_args, expr_span)
+            }
+            Self::QUASI_FN_BUILDER => Self::parse_builder(config_builder, expr_args, expr_span),
             _other => abort!(
                 expr_span,
                 format!(
@@ -177,6 +503,25 @@ impl Config {
                         Self::QUASI_FN_ITEMS_SUFFIX,
                         Self::QUASI_FN_ENUM_SPEC,
                         Self::QUASI_FN_STRUCT_SPEC,
+                        Self::QUASI_FN_FOREIGN_MOD,
+                        Self::QUASI_FN_EXTERN,
+                        Self::QUASI_FN_TIMESTAMP_MAPPING,
+                        Self::QUASI_FN_DURATION_MAPPING,
+                        Self::QUASI_FN_JSON_MAPPING,
+                        Self::QUASI_FN_ANY,
+                        Self::QUASI_FN_FIELD_TYPE,
+                        Self::QUASI_FN_TYPE_MAPPING,
+                        Self::QUASI_FN_NEWTYPE,
+                        Self::QUASI_FN_OPTIONAL,
+                        Self::QUASI_FN_VALIDATE_RULES,
+                        Self::QUASI_FN_VALIDATOR,
+                        Self::QUASI_FN_ONEOF_ENUM,
+                        Self::QUASI_FN_TRANSPARENT,
+                        Self::QUASI_FN_DEFAULT_POLICY,
+                        Self::QUASI_FN_RENAME,
+                        Self::QUASI_FN_FLATTEN,
+                        Self::QUASI_FN_PRIVATE_FIELDS,
+                        Self::QUASI_FN_BUILDER,
                     ]
                     .join(", ")
                 ),
@@ -377,13 +722,20 @@ impl Config {
         call_args: &mut Punctuated<Expr, Token![,]>,
         expr_span: &Span,
     ) {
-        if call_args.len() != 2 {
-            abort!(expr_span, "Parameter must have 2 arguments");
+        if !(2..=4).contains(&call_args.len()) {
+            abort!(expr_span, "Parameter must have 2 to 4 arguments");
         }
 
         let mut call_args_iter = call_args.iter();
 
-        let fqn = match call_args_iter.next().unwrap() {
+        // the named view of the struct, e.g. `User as UserForCreate`
+        let (fqn_expr, view) = match call_args_iter.next().unwrap() {
+            Expr::Cast(ExprCast { expr, ty, .. }) => {
+                (expr.as_ref(), Some(Self::parse_view_ident(ty)))
+            }
+            expr_ => (expr_, None),
+        };
+        let fqn = match fqn_expr {
             Expr::Path(path_expr) => path_expr.path.clone(),
             expr_ => {
                 abort!(
@@ -393,14 +745,101 @@ impl Config {
             }
         };
 
+        let mut modifiers = HashMap::new();
+        let mut defaults = HashMap::new();
+        let mut field_views = HashMap::new();
+        let mut nested_paths = Vec::new();
+        let mut wildcard = false;
+        let mut excluded = Vec::new();
         let fields = match call_args_iter.next().unwrap() {
             Expr::Array(array_expr) => array_expr
                 .elems
                 .iter()
-                .map(|field_expr| {
+                .filter_map(|field_expr| {
+                    match field_expr {
+                        // the wildcard, e.g. `*`
+                        Expr::Infer(_) => {
+                            wildcard = true;
+                            return None;
+                        }
+                        // the field opted out of the wildcard, e.g. `!field1`
+                        Expr::Unary(ExprUnary {
+                            op: UnOp::Not(_),
+                            expr,
+                            ..
+                        }) => match expr.as_ref() {
+                            Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => {
+                                excluded.push(path_expr.path.get_ident().unwrap().clone());
+                                return None;
+                            }
+                            expr_ => abort!(
+                                expr_,
+                                "Field must be a single ident literal, e.g. `!field1`"
+                            ),
+                        },
+                        _ => (),
+                    }
+                    // the field with modifier or default, e.g. `field1 = non_empty`
+                    let (field_expr, value_expr) = match field_expr {
+                        Expr::Assign(ExprAssign { left, right, .. }) => {
+                            (left.as_ref(), Some(right.as_ref()))
+                        }
+                        field_expr => (field_expr, None),
+                    };
+                    // the field of the named view type, e.g. `user as UserForCreate`
+                    let (field_expr, field_view) = match field_expr {
+                        Expr::Cast(ExprCast { expr, ty, .. }) => {
+                            (expr.as_ref(), Some(Self::parse_view_ident(ty)))
+                        }
+                        field_expr => (field_expr, None),
+                    };
+                    // the nested field path, e.g. `order.customer`
+                    if let Expr::Field(_) = field_expr {
+                        if value_expr.is_some() || field_view.is_some() {
+                            abort!(
+                                field_expr,
+                                "Nested field path can not have a modifier, default value or view name"
+                            );
+                        }
+                        nested_paths.push(Self::parse_nested_field_path(field_expr));
+                        return None;
+                    }
                     if let Expr::Path(field_path_expr) = field_expr {
                         if field_path_expr.path.segments.len() == 1 {
-                            return field_path_expr.path.segments.first().unwrap().ident.clone();
+                            let field =
+                                field_path_expr.path.segments.first().unwrap().ident.clone();
+                            if let Some(field_view) = field_view {
+                                field_views.insert(field.to_string(), field_view);
+                            }
+                            match value_expr {
+                                Some(Expr::Call(ExprCall { func, args, .. }))
+                                    if matches!(func.as_ref(), Expr::Path(path_expr) if path_expr.path.is_ident("variant")) =>
+                                {
+                                    modifiers.insert(
+                                        field.to_string(),
+                                        FieldModifier::Variant(Self::parse_variant(args)),
+                                    );
+                                }
+                                Some(Expr::Path(path_expr))
+                                    if path_expr.path.is_ident("default") =>
+                                {
+                                    defaults.insert(field.to_string(), FieldDefault::Default);
+                                }
+                                Some(call_expr @ Expr::Call(_)) => {
+                                    defaults.insert(
+                                        field.to_string(),
+                                        FieldDefault::Call(call_expr.clone()),
+                                    );
+                                }
+                                Some(modifier_expr) => {
+                                    modifiers.insert(
+                                        field.to_string(),
+                                        Self::parse_field_modifier(modifier_expr),
+                                    );
+                                }
+                                None => (),
+                            }
+                            return Some(field);
                         }
                     }
                     abort!(
@@ -417,7 +856,98 @@ impl Config {
             }
         };
 
-        let struct_spec = SpecTreeLeaf::new_struct_spec(fqn, fields);
+        // the optional arguments, e.g. `skip = [field1]`
+        let mut skips = None;
+        let mut renames = None;
+        for arg_expr in call_args_iter {
+            let (arg_name, value_expr) = match arg_expr {
+                Expr::Assign(ExprAssign { left, right, .. }) => match left.as_ref() {
+                    Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => (
+                        path_expr.path.get_ident().unwrap().to_string(),
+                        right.as_ref(),
+                    ),
+                    expr_ => abort!(expr_, "Argument name must be one of: `skip`, `rename`"),
+                },
+                expr_ => abort!(
+                    expr_,
+                    "Argument must be one of: `skip = [field1]`, `rename = { field1 => name1 }`"
+                ),
+            };
+            match arg_name.as_str() {
+                "skip" if skips.is_none() => skips = Some(Self::parse_field_skips(value_expr)),
+                "rename" if renames.is_none() => {
+                    renames = Some(Self::parse_field_renames(value_expr))
+                }
+                "skip" | "rename" => abort!(
+                    arg_expr,
+                    format!("Multiple `{}` arguments are not allowed", arg_name)
+                ),
+                _ => abort!(arg_expr, "Argument name must be one of: `skip`, `rename`"),
+            }
+        }
+        let skips = skips.unwrap_or_default();
+        let renames = renames.unwrap_or_default();
+        if let Some((field, _)) = renames.iter().find(|(field, _)| {
+            skips
+                .fields
+                .iter()
+                .any(|(skipped_field, _)| skipped_field == field)
+        }) {
+            abort!(field, "Field can not be both skipped and renamed");
+        }
+        if let Some((field, _)) = skips.fields.iter().find(|(field, _)| {
+            fields.contains(field) || field_views.contains_key(&field.to_string())
+        }) {
+            abort!(field, "Field can not be both listed and skipped");
+        }
+
+        if let Some(field) = excluded.first() {
+            if !wildcard {
+                abort!(
+                    field,
+                    "Field opt-out requires the `*` wildcard, e.g. `[*, !field1]`"
+                );
+            }
+        }
+        if let Some(field) = excluded.iter().find(|field| fields.contains(field)) {
+            abort!(field, "Field can not be both listed and opted out");
+        }
+        if wildcard {
+            config_builder
+                .wildcards
+                .as_mut()
+                .expect("Expected wildcards to be Some")
+                .push(WildcardSpec {
+                    fqn: fqn.clone(),
+                    view: view.clone(),
+                    excluded,
+                });
+        }
+
+        if !nested_paths.is_empty() {
+            config_builder
+                .nested_fields
+                .as_mut()
+                .expect("Expected nested_fields to be Some")
+                .push(NestedFieldsSpec {
+                    fqn: fqn.clone(),
+                    view: view.clone(),
+                    paths: nested_paths,
+                });
+        }
+
+        let mut struct_spec = SpecTreeLeaf::new_struct_spec_with_modifiers(
+            fqn,
+            view,
+            fields,
+            modifiers,
+            defaults,
+            field_views,
+            skips,
+        );
+        if let SpecTreeLeaf::Struct(ref mut struct_spec) = struct_spec {
+            struct_spec.set_field_renames(renames);
+        }
 
         config_builder
             .spec_tree
@@ -426,6 +956,169 @@ impl Config {
             .push(struct_spec);
     }
 
+    /// Parses the fields excluded from the mirror struct, e.g.
+    /// `skip = [debug_info, legacy_id = reject, deprecated()]`.
+    fn parse_field_skips(expr: &Expr) -> FieldSkips {
+        let elems = match expr {
+            Expr::Array(array_expr) => &array_expr.elems,
+            expr_ => abort!(
+                expr_,
+                "Argument must be an array of fields ident literals, e.g. `skip = [field1, field2]`"
+            ),
+        };
+
+        let mut skips = FieldSkips::default();
+        for elem in elems {
+            // the field rejected if it is set, e.g. `field1 = reject`
+            let (skip_expr, skip) = match elem {
+                Expr::Assign(ExprAssign { left, right, .. }) => match right.as_ref() {
+                    Expr::Path(path_expr) if path_expr.path.is_ident("reject") => {
+                        (left.as_ref(), FieldSkip::Reject)
+                    }
+                    expr_ => abort!(
+                        expr_,
+                        "Skipped field may only be `reject`, e.g. `field1 = reject`"
+                    ),
+                },
+                elem => (elem, FieldSkip::Discard),
+            };
+            match skip_expr {
+                Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => {
+                    skips
+                        .fields
+                        .push((path_expr.path.get_ident().unwrap().clone(), skip));
+                }
+                // the `#[deprecated]` fields
+                Expr::Call(ExprCall { func, args, .. })
+                    if args.is_empty()
+                        && matches!(func.as_ref(), Expr::Path(path_expr) if path_expr.path.is_ident("deprecated")) =>
+                {
+                    skips.deprecated = Some(skip);
+                }
+                expr_ => abort!(
+                    expr_,
+                    "Skipped field must be a single ident literal or `deprecated()`, e.g. `field1`"
+                ),
+            }
+        }
+
+        skips
+    }
+
+    /// Parses the fields renames, e.g. `rename = { pb_user_id => user_id }`,
+    /// normalized into the array, see `normalize_field_lists`.
+    fn parse_field_renames(expr: &Expr) -> Vec<(Ident, Ident)> {
+        let Expr::Array(array_expr) = expr else {
+            abort!(
+                expr,
+                "Argument must be the map of fields renames, e.g. `rename = { field1 => name1 }`"
+            );
+        };
+
+        let mut renames: Vec<(Ident, Ident)> = Vec::new();
+        for elem in &array_expr.elems {
+            let (field, renamed) = match elem {
+                Expr::Assign(ExprAssign { left, right, .. }) => {
+                    match (left.as_ref(), right.as_ref()) {
+                        (Expr::Path(field_expr), Expr::Path(renamed_expr))
+                            if field_expr.path.get_ident().is_some()
+                                && renamed_expr.path.get_ident().is_some() =>
+                        {
+                            (
+                                field_expr.path.get_ident().unwrap().clone(),
+                                renamed_expr.path.get_ident().unwrap().clone(),
+                            )
+                        }
+                        _ => abort!(
+                            elem,
+                            "Field rename must be a pair of ident literals, e.g. `field1 => name1`"
+                        ),
+                    }
+                }
+                _ => abort!(
+                    elem,
+                    "Field rename must be a pair of ident literals, e.g. `field1 => name1`"
+                ),
+            };
+            if renames
+                .iter()
+                .any(|(renamed_field, _)| *renamed_field == field)
+            {
+                abort!(field, "Multiple renames of the field are not allowed");
+            }
+            renames.push((field, renamed));
+        }
+
+        renames
+    }
+
+    /// Parses the nested field path, e.g. `order.customer.address`.
+    fn parse_nested_field_path(expr: &Expr) -> Vec<Ident> {
+        match expr {
+            Expr::Field(ExprField {
+                base,
+                member: Member::Named(field),
+                ..
+            }) => {
+                let mut path = Self::parse_nested_field_path(base);
+                path.push(field.clone());
+                path
+            }
+            Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => {
+                vec![path_expr.path.get_ident().unwrap().clone()]
+            }
+            expr_ => abort!(
+                expr_,
+                "Field must be a single ident literal or a nested field path, e.g. `field1.field2`"
+            ),
+        }
+    }
+
+    /// Parses the named view ident, e.g. `UserForCreate` in
+    /// `User as UserForCreate`.
+    fn parse_view_ident(ty: &Type) -> Ident {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.get_ident().is_some() => {
+                path.get_ident().unwrap().clone()
+            }
+            ty_ => abort!(
+                ty_,
+                "View name must be a single ident literal, e.g. `User as UserForCreate`"
+            ),
+        }
+    }
+
+    /// Parses the `variant(Create)` modifier argument.
+    fn parse_variant(args: &Punctuated<Expr, Token![,]>) -> Ident {
+        match args.first() {
+            Some(Expr::Path(path_expr))
+                if args.len() == 1 && path_expr.path.get_ident().is_some() =>
+            {
+                path_expr.path.get_ident().unwrap().clone()
+            }
+            _ => abort!(
+                args,
+                "Argument must be a single oneof variant ident literal, e.g. `variant(Create)`"
+            ),
+        }
+    }
+
+    fn parse_field_modifier(expr: &Expr) -> FieldModifier {
+        match expr {
+            Expr::Path(path_expr) if path_expr.path.is_ident("non_empty") => {
+                FieldModifier::NonEmpty
+            }
+            Expr::Path(path_expr) if path_expr.path.is_ident("non_empty_vec") => {
+                FieldModifier::NonEmptyVec
+            }
+            Expr::Path(path_expr) if path_expr.path.is_ident("non_zero") => FieldModifier::NonZero,
+            expr_ => abort!(
+                expr_,
+                "Field modifier must be one of: `non_empty`, `non_empty_vec`, `non_zero`, `variant(Variant)`, `default` or a function call, e.g. `crate::defaults::page()`",
+            ),
+        }
+    }
+
     /// Parser for Self::QUASI_FN_ENUM_SPEC
     fn parse_enum_spec(
         config_builder: &mut ConfigBuilder,
@@ -456,81 +1149,1902 @@ impl Config {
             .expect("Expected spec_tree to be Some")
             .push(enum_spec);
     }
-}
 
-impl Config {
-    pub fn orig_item_typepath<I: IntoIterator<Item = String>>(&self, ident_path: I) -> Path {
-        let absolute_part = self.orig_mod_path.clone();
-        self.item_typepath(absolute_part, ident_path)
-    }
+    /// Parser for Self::QUASI_FN_FOREIGN_MOD
+    fn parse_foreign_mod(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 {
+            abort!(expr_span, "Parameter must have 2 arguments");
+        }
 
-    pub fn this_item_typepath<I: IntoIterator<Item = String>>(&self, ident_path: I) -> Path {
-        let absolute_part = self.this_mod_path.clone();
-        self.item_typepath(absolute_part, ident_path)
-    }
+        let mut call_args_iter = call_args.iter();
 
-    fn item_typepath<I: IntoIterator<Item = String>>(
-        &self,
-        mut absolute_part: Path,
-        ident_path: I,
-    ) -> Path {
-        for ident in self.source.fqn.segments.iter() {
-            absolute_part.segments.push(ident.clone());
-        }
-        for ident in ident_path {
-            absolute_part.segments.push(syn::PathSegment {
-                ident: Ident::new(ident.as_str(), Span::call_site()),
-                arguments: syn::PathArguments::None,
-            });
+        let fqn = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => abort!(
+                expr_,
+                "Argument must be a module path literal relative to `with_original_mod` argument, e.g. `root::submodule`",
+            ),
+        };
+
+        let this_mod_path = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.segments.first().unwrap().ident == "crate" => {
+                path_expr.path.clone()
+            }
+            expr_ => abort!(
+                expr_,
+                "Argument must be an absolute module path literal, e.g. `crate::proto::root`",
+            ),
+        };
+
+        let foreign_mods = config_builder
+            .foreign_mods
+            .as_mut()
+            .expect("Expected foreign_mods to be Some");
+
+        if foreign_mods
+            .iter()
+            .any(|foreign_mod| foreign_mod.fqn == fqn)
+        {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same module are not allowed",
+                    Self::QUASI_FN_FOREIGN_MOD
+                ),
+            );
         }
-        absolute_part
+
+        foreign_mods.push(ForeignMod { fqn, this_mod_path });
     }
-}
 
-/// Contains config structures and functions to collect and operate on a tree
-/// of specificated structs and enums.
-pub(crate) mod spec_tree {
-    use std::collections::hash_map::Entry;
-    use std::collections::HashMap;
+    /// Parser for Self::QUASI_FN_EXTERN
+    fn parse_extern(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
 
-    use proc_macro2::Span;
-    use proc_macro_error::abort;
-    use syn::punctuated::Punctuated;
-    use syn::Ident;
-    use syn::Path;
-    use syn::PathSegment;
+        // the `=>` mapping is normalized into the assignment, see
+        // `normalize_extern_mappings`
+        let Expr::Assign(assign_expr) = call_args.first().unwrap() else {
+            abort!(
+                call_args.first().unwrap(),
+                "Argument must be the modules mapping, e.g. `::common_proto::generated => ::common_proto::unwrapped`",
+            );
+        };
+        let mut paths = [&assign_expr.left, &assign_expr.right]
+            .into_iter()
+            .map(|expr| match expr.as_ref() {
+                Expr::Path(path_expr)
+                    if path_expr.path.leading_colon.is_some()
+                        || path_expr.path.segments.first().unwrap().ident == "crate" =>
+                {
+                    path_expr.path.clone()
+                }
+                expr_ => abort!(
+                    expr_,
+                    "Argument must be an absolute module path literal, e.g. `::common_proto::generated`",
+                ),
+            });
+        let orig_mod_path = paths.next().unwrap();
+        let this_mod_path = paths.next().unwrap();
 
-    #[derive(Clone, Debug)]
-    pub(crate) struct StructSpec {
-        fqn: Path,
-        fields: Vec<Ident>,
-    }
+        let externs = config_builder
+            .externs
+            .as_mut()
+            .expect("Expected externs to be Some");
 
-    impl StructSpec {
-        pub fn fields_map(&self) -> HashMap<String, &Ident> {
-            let mut hashmap = HashMap::new();
-            for field in self.fields.iter() {
-                hashmap.insert(field.to_string(), field);
-            }
-            hashmap
+        if externs
+            .iter()
+            .any(|extern_| extern_.orig_mod_path == orig_mod_path)
+        {
+            abort!(
+                orig_mod_path,
+                format!(
+                    "Multiple `{}` parameters for the same module are not allowed",
+                    Self::QUASI_FN_EXTERN
+                ),
+            );
         }
-    }
 
-    #[derive(Clone, Debug)]
-    pub(crate) struct EnumSpec {
-        fqn: Path,
+        externs.push(Extern {
+            orig_mod_path,
+            this_mod_path,
+        });
     }
 
-    #[derive(Clone, Debug)]
-    #[allow(dead_code)]
-    pub(crate) enum SpecTreeLeaf {
-        Struct(StructSpec),
+    /// Parser for Self::QUASI_FN_TIMESTAMP_MAPPING
+    fn parse_timestamp_mapping(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if config_builder.timestamp_mapping.is_some() {
+            abort!(
+                expr_span,
+                format!(
+                    "Multiple `{}` parameters are not allowed",
+                    Self::QUASI_FN_TIMESTAMP_MAPPING
+                ),
+            )
+        }
+
+        let mapping = Self::parse_time_mapping(call_args, expr_span);
+        config_builder.timestamp_mapping(Some(mapping));
+    }
+
+    /// Parser for Self::QUASI_FN_DURATION_MAPPING
+    fn parse_duration_mapping(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if config_builder.duration_mapping.is_some() {
+            abort!(
+                expr_span,
+                format!(
+                    "Multiple `{}` parameters are not allowed",
+                    Self::QUASI_FN_DURATION_MAPPING
+                ),
+            )
+        }
+
+        let mapping = Self::parse_time_mapping(call_args, expr_span);
+        config_builder.duration_mapping(Some(mapping));
+    }
+
+    /// Parser for Self::QUASI_FN_JSON_MAPPING
+    fn parse_json_mapping(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if config_builder.json_mapping.is_some() {
+            abort!(
+                expr_span,
+                format!(
+                    "Multiple `{}` parameters are not allowed",
+                    Self::QUASI_FN_JSON_MAPPING
+                ),
+            )
+        }
+
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let mapping = match call_args.first().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.is_ident("serde_json") => {
+                JsonMapping::SerdeJson
+            }
+            expr_ => abort!(expr_, "Parameter argument must be one of: `serde_json`"),
+        };
+
+        if mapping == JsonMapping::SerdeJson && !cfg!(feature = "serde_json") {
+            abort!(
+                call_args,
+                "The `serde_json` mapping requires the `serde_json` feature of prost-unwrap to be enabled",
+            );
+        }
+        config_builder.json_mapping(Some(mapping));
+    }
+
+    /// Parser for Self::QUASI_FN_ANY
+    fn parse_any(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 {
+            abort!(expr_span, "Parameter must have 2 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+
+        let (fqn, field) = Self::parse_field_path(call_args_iter.next().unwrap());
+
+        let types = match call_args_iter.next().unwrap() {
+            Expr::Array(array_expr) if !array_expr.elems.is_empty() => array_expr
+                .elems
+                .iter()
+                .map(|type_expr| match type_expr {
+                    Expr::Path(type_path_expr) => type_path_expr.path.clone(),
+                    expr_ => abort!(
+                        expr_,
+                        "Message type must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`",
+                    ),
+                })
+                .collect::<Vec<Path>>(),
+            expr_ => abort!(
+                expr_,
+                "Argument must be a non-empty array of message type path literals, e.g. `[MsgA, MsgB]`",
+            ),
+        };
+
+        let anys = config_builder
+            .anys
+            .as_mut()
+            .expect("Expected anys to be Some");
+
+        if anys.iter().any(|any| any.fqn == fqn && any.field == field) {
+            abort!(
+                field,
+                format!(
+                    "Multiple `{}` parameters for the same field are not allowed",
+                    Self::QUASI_FN_ANY
+                ),
+            );
+        }
+
+        anys.push(AnySpec { fqn, field, types });
+    }
+
+    /// Parser for Self::QUASI_FN_FIELD_TYPE
+    fn parse_field_type(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 4 {
+            abort!(expr_span, "Parameter must have 4 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+        let (fqn, field) = Self::parse_field_path(call_args_iter.next().unwrap());
+        let custom_type = Self::parse_custom_type(call_args_iter);
+
+        let field_types = config_builder
+            .field_types
+            .as_mut()
+            .expect("Expected field_types to be Some");
+
+        if field_types
+            .iter()
+            .any(|field_type| field_type.fqn == fqn && field_type.field == field)
+        {
+            abort!(
+                field,
+                format!(
+                    "Multiple `{}` parameters for the same field are not allowed",
+                    Self::QUASI_FN_FIELD_TYPE
+                ),
+            );
+        }
+
+        field_types.push(FieldTypeSpec {
+            fqn,
+            field,
+            custom_type,
+        });
+    }
+
+    /// Parser for Self::QUASI_FN_TYPE_MAPPING
+    fn parse_type_mapping(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 4 {
+            abort!(expr_span, "Parameter must have 4 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+        let orig_path = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => abort!(
+                expr_,
+                "Argument must be a type path literal relative to `with_original_mod` argument, e.g. `root::Something`, or an absolute path of prost `extern_path` type",
+            ),
+        };
+        let custom_type = Self::parse_custom_type(call_args_iter);
+
+        let type_mappings = config_builder
+            .type_mappings
+            .as_mut()
+            .expect("Expected type_mappings to be Some");
+
+        if type_mappings
+            .iter()
+            .any(|type_mapping| type_mapping.orig_path == orig_path)
+        {
+            abort!(
+                orig_path,
+                format!(
+                    "Multiple `{}` parameters for the same type are not allowed",
+                    Self::QUASI_FN_TYPE_MAPPING
+                ),
+            );
+        }
+
+        type_mappings.push(TypeMappingSpec {
+            orig_path,
+            custom_type,
+        });
+    }
+
+    /// Parser for Self::QUASI_FN_NEWTYPE
+    fn parse_newtype(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 {
+            abort!(expr_span, "Parameter must have 2 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+        let (fqn, field) = Self::parse_field_path(call_args_iter.next().unwrap());
+
+        let ident = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => {
+                path_expr.path.get_ident().unwrap().clone()
+            }
+            expr_ => abort!(
+                expr_,
+                "Argument must be a single ident literal, e.g. `UserId`"
+            ),
+        };
+
+        let newtypes = config_builder
+            .newtypes
+            .as_mut()
+            .expect("Expected newtypes to be Some");
+
+        if newtypes
+            .iter()
+            .any(|newtype| newtype.fqn == fqn && newtype.field == field)
+        {
+            abort!(
+                field,
+                format!(
+                    "Multiple `{}` parameters for the same field are not allowed",
+                    Self::QUASI_FN_NEWTYPE
+                ),
+            );
+        }
+
+        newtypes.push(NewtypeSpec { fqn, field, ident });
+    }
+
+    /// Parser for Self::QUASI_FN_OPTIONAL
+    fn parse_optional(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 {
+            abort!(expr_span, "Parameter must have 2 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+
+        let fqn = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let fields = match call_args_iter.next().unwrap() {
+            Expr::Array(array_expr) => array_expr
+                .elems
+                .iter()
+                .map(|field_expr| match field_expr {
+                    Expr::Path(field_path_expr) if field_path_expr.path.get_ident().is_some() => {
+                        field_path_expr.path.get_ident().unwrap().clone()
+                    }
+                    expr_ => abort!(expr_, "Field must be a single ident literal, e.g. `field1`",),
+                })
+                .collect::<Vec<Ident>>(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be an array of fields ident literals, e.g. `[field1, field2]`",
+                );
+            }
+        };
+
+        let optionals = config_builder
+            .optionals
+            .as_mut()
+            .expect("Expected optionals to be Some");
+
+        if optionals.iter().any(|optional| optional.fqn == fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same struct are not allowed",
+                    Self::QUASI_FN_OPTIONAL
+                ),
+            );
+        }
+
+        optionals.push(OptionalSpec { fqn, fields });
+    }
+
+    /// Parser for Self::QUASI_FN_ONEOF_ENUM
+    fn parse_oneof_enum(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let fqn = match call_args.first().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let oneof_enums = config_builder
+            .oneof_enums
+            .as_mut()
+            .expect("Expected oneof_enums to be Some");
+
+        if oneof_enums.contains(&fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same struct are not allowed",
+                    Self::QUASI_FN_ONEOF_ENUM
+                ),
+            );
+        }
+
+        oneof_enums.push(fqn);
+    }
+
+    /// Parser for Self::QUASI_FN_TRANSPARENT
+    fn parse_transparent(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let fqn = match call_args.first().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let transparents = config_builder
+            .transparents
+            .as_mut()
+            .expect("Expected transparents to be Some");
+
+        if transparents.contains(&fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same struct are not allowed",
+                    Self::QUASI_FN_TRANSPARENT
+                ),
+            );
+        }
+
+        transparents.push(fqn);
+    }
+
+    /// Parser for Self::QUASI_FN_PRIVATE_FIELDS
+    fn parse_private_fields(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let fqn = match call_args.first().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let privates = config_builder
+            .privates
+            .as_mut()
+            .expect("Expected privates to be Some");
+
+        if privates.contains(&fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same struct are not allowed",
+                    Self::QUASI_FN_PRIVATE_FIELDS
+                ),
+            );
+        }
+
+        privates.push(fqn);
+    }
+
+    /// Parser for Self::QUASI_FN_BUILDER
+    fn parse_builder(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let fqn = match call_args.first().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let builders = config_builder
+            .builders
+            .as_mut()
+            .expect("Expected builders to be Some");
+
+        if builders.contains(&fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same struct are not allowed",
+                    Self::QUASI_FN_BUILDER
+                ),
+            );
+        }
+
+        builders.push(fqn);
+    }
+
+    /// Parser for Self::QUASI_FN_RENAME
+    fn parse_rename(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 {
+            abort!(expr_span, "Parameter must have 2 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+
+        let fqn = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+        let ident = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.get_ident().is_some() => {
+                path_expr.path.get_ident().unwrap().clone()
+            }
+            expr_ => abort!(
+                expr_,
+                "Argument must be a single ident literal, e.g. `Something`"
+            ),
+        };
+
+        let renames = config_builder
+            .renames
+            .as_mut()
+            .expect("Expected renames to be Some");
+
+        if renames.iter().any(|rename| rename.fqn == fqn) {
+            abort!(
+                fqn,
+                format!(
+                    "Multiple `{}` parameters for the same item are not allowed",
+                    Self::QUASI_FN_RENAME
+                ),
+            );
+        }
+
+        renames.push(RenameSpec { fqn, ident });
+    }
+
+    /// Parser for Self::QUASI_FN_FLATTEN
+    fn parse_flatten(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.is_empty() || call_args.len() > 2 {
+            abort!(expr_span, "Parameter must have 1 or 2 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+        let (fqn, field) = Self::parse_field_path(call_args_iter.next().unwrap());
+
+        // the flattened fields are prefixed with the field name by default
+        let prefix = match call_args_iter.next() {
+            None => format!("{}_", field.unraw()),
+            Some(Expr::Assign(ExprAssign { left, right, .. })) if matches!(left.as_ref(), Expr::Path(path_expr) if path_expr.path.is_ident("prefix")) => {
+                match right.as_ref() {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(str_lit),
+                        ..
+                    }) => str_lit.value(),
+                    expr_ => abort!(expr_, "Prefix must be a string literal, e.g. `\"name_\"`"),
+                }
+            }
+            Some(expr_) => abort!(
+                expr_,
+                "Argument must be the prefix of the flattened fields, e.g. `prefix = \"name_\"`"
+            ),
+        };
+
+        let flattens = config_builder
+            .flattens
+            .as_mut()
+            .expect("Expected flattens to be Some");
+
+        if flattens
+            .iter()
+            .any(|flatten| flatten.fqn == fqn && flatten.field == field)
+        {
+            abort!(
+                field,
+                format!(
+                    "Multiple `{}` parameters for the same field are not allowed",
+                    Self::QUASI_FN_FLATTEN
+                ),
+            );
+        }
+
+        flattens.push(FlattenSpec { fqn, field, prefix });
+    }
+
+    /// Parser for Self::QUASI_FN_VALIDATE_RULES
+    fn parse_validate_rules(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let str_lit = match call_args.first().unwrap() {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(str_lit),
+                ..
+            }) => str_lit,
+            expr_ => abort!(
+                expr_,
+                "Argument must be a string literal path to the `.proto` file, e.g. `\"proto/acme.proto\"`"
+            ),
+        };
+
+        let contents = fs::read_to_string(PathBuf::from(str_lit.value()))
+            .map_err(|e| {
+                abort!(
+                    str_lit,
+                    format!(
+                        "Failed to load validation rules from {:?}: {} (cwd: {:?})",
+                        &str_lit.value(),
+                        e,
+                        std::env::current_dir().expect("Expected current dir to be present")
+                    ),
+                )
+            })
+            .unwrap();
+
+        let proto_rules = ProtoRules::parse(&contents, str_lit.span())
+            .map_err(|e| {
+                abort!(
+                    str_lit,
+                    format!(
+                        "Failed to parse validation rules from {:?}: {}",
+                        &str_lit.value(),
+                        e
+                    ),
+                )
+            })
+            .unwrap();
+
+        config_builder
+            .validate_rules
+            .as_mut()
+            .expect("Expected validate_rules to be Some")
+            .push(proto_rules);
+    }
+
+    /// Parser for Self::QUASI_FN_VALIDATOR
+    fn parse_validator(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if call_args.len() != 2 && call_args.len() != 3 {
+            abort!(expr_span, "Parameter must have 2 or 3 arguments");
+        }
+
+        let mut call_args_iter = call_args.iter();
+
+        let fqn = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => {
+                abort!(
+                    expr_,
+                    "Argument must be a path literal relative to `with_original_mod` argument, e.g. `root::Something`"
+                );
+            }
+        };
+
+        let validator = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => path_expr.path.clone(),
+            expr_ => abort!(
+                expr_,
+                "Argument must be a function path literal, e.g. `crate::validate::something`"
+            ),
+        };
+
+        let original = match call_args_iter.next() {
+            None => false,
+            Some(Expr::Path(path_expr)) if path_expr.path.is_ident("original") => true,
+            Some(expr_) => abort!(
+                expr_,
+                "Argument must be `original`, if the validator accepts the original struct"
+            ),
+        };
+
+        config_builder
+            .validators
+            .as_mut()
+            .expect("Expected validators to be Some")
+            .push(ValidatorSpec {
+                fqn,
+                validator,
+                original,
+            });
+    }
+
+    /// Parses the field path argument, e.g. `root::Something.field`.
+    fn parse_field_path(expr: &Expr) -> (Path, Ident) {
+        if let Expr::Field(ExprField {
+            base,
+            member: Member::Named(field),
+            ..
+        }) = expr
+        {
+            if let Expr::Path(path_expr) = base.as_ref() {
+                return (path_expr.path.clone(), field.clone());
+            }
+        }
+        abort!(
+            expr,
+            "Argument must be a field path literal relative to `with_original_mod` argument, e.g. `root::Something.field`"
+        );
+    }
+
+    /// Parses the mirror type and conversion functions arguments, e.g.
+    /// `uuid::Uuid, try_from = parse_uuid, into = Uuid::to_string`.
+    fn parse_custom_type<'a, I: Iterator<Item = &'a Expr>>(mut call_args_iter: I) -> CustomType {
+        let ty = match call_args_iter.next().unwrap() {
+            Expr::Path(path_expr) => Type::Path(TypePath {
+                qself: None,
+                path: path_expr.path.clone(),
+            }),
+            expr_ => abort!(
+                expr_,
+                "Argument must be a type path literal, e.g. `uuid::Uuid`"
+            ),
+        };
+
+        let mut try_from_fn = None;
+        let mut into_fn = None;
+        for expr in call_args_iter {
+            let (name, function) = match expr {
+                Expr::Assign(ExprAssign { left, right, .. }) => match left.as_ref() {
+                    Expr::Path(path_expr) if path_expr.path.is_ident("try_from") => {
+                        (&mut try_from_fn, right.as_ref())
+                    }
+                    Expr::Path(path_expr) if path_expr.path.is_ident("into") => {
+                        (&mut into_fn, right.as_ref())
+                    }
+                    expr_ => abort!(expr_, "Argument name must be one of: `try_from`, `into`"),
+                },
+                expr_ => abort!(
+                    expr_,
+                    "Argument must be a named conversion function, e.g. `try_from = parse_uuid`"
+                ),
+            };
+            if name.is_some() {
+                abort!(
+                    expr,
+                    "Multiple conversion functions of the same name are not allowed"
+                );
+            }
+            *name = Some(function.clone());
+        }
+
+        match (try_from_fn, into_fn) {
+            (Some(try_from_fn), Some(into_fn)) => CustomType {
+                ty,
+                try_from_fn,
+                into_fn,
+            },
+            (None, _) => abort!(ty, "Parameter must have `try_from` argument"),
+            (_, None) => abort!(ty, "Parameter must have `into` argument"),
+        }
+    }
+
+    /// Parser for Self::QUASI_FN_DEFAULT_POLICY
+    fn parse_default_policy(
+        config_builder: &mut ConfigBuilder,
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) {
+        if config_builder.default_policy.is_some() {
+            abort!(
+                expr_span,
+                format!(
+                    "Multiple `{}` parameters are not allowed",
+                    Self::QUASI_FN_DEFAULT_POLICY
+                ),
+            )
+        }
+
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let policy = match call_args.first().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.is_ident("require_messages") => {
+                DefaultPolicy::RequireMessages
+            }
+            expr_ => abort!(
+                expr_,
+                "Parameter argument must be one of: `require_messages`",
+            ),
+        };
+        config_builder.default_policy(Some(policy));
+    }
+
+    fn parse_time_mapping(
+        call_args: &mut Punctuated<Expr, Token![,]>,
+        expr_span: &Span,
+    ) -> TimeMapping {
+        if call_args.len() != 1 {
+            abort!(expr_span, "Parameter must have 1 argument");
+        }
+
+        let mapping = match call_args.first().unwrap() {
+            Expr::Path(path_expr) if path_expr.path.is_ident("std") => TimeMapping::Std,
+            Expr::Path(path_expr) if path_expr.path.is_ident("chrono") => TimeMapping::Chrono,
+            Expr::Path(path_expr) if path_expr.path.is_ident("time") => TimeMapping::Time,
+            expr_ => abort!(
+                expr_,
+                "Parameter argument must be one of: `std`, `chrono`, `time`",
+            ),
+        };
+
+        if mapping == TimeMapping::Chrono && !cfg!(feature = "chrono") {
+            abort!(
+                call_args,
+                "The `chrono` mapping requires the `chrono` feature of prost-unwrap to be enabled",
+            );
+        }
+        if mapping == TimeMapping::Time && !cfg!(feature = "time") {
+            abort!(
+                call_args,
+                "The `time` mapping requires the `time` feature of prost-unwrap to be enabled",
+            );
+        }
+
+        mapping
+    }
+}
+
+impl Config {
+    /// Returns the absolute path of the mirrored item for the absolute path of
+    /// prost `extern_path` item, if it is covered by one of the `with_extern`
+    /// parameters; the longest matching path is picked.
+    pub fn extern_item_typepath(&self, path: &Path) -> Option<Path> {
+        let item_path = path_to_strings(path);
+        self.externs
+            .iter()
+            .filter(|extern_| extern_.orig_mod_path.leading_colon == path.leading_colon)
+            .map(|extern_| (extern_, path_to_strings(&extern_.orig_mod_path)))
+            .filter(|(_, orig_mod_path)| item_path.starts_with(orig_mod_path))
+            .max_by_key(|(_, orig_mod_path)| orig_mod_path.len())
+            .map(|(extern_, orig_mod_path)| {
+                let mut this_path = extern_.this_mod_path.clone();
+                this_path
+                    .segments
+                    .extend(path.segments.iter().skip(orig_mod_path.len()).cloned());
+                this_path
+            })
+    }
+
+    /// Returns the absolute path of the mirrored item for the item path
+    /// relative to `with_original_mod` argument, e.g. `root::MsgA`, either
+    /// within this module, or within one of the foreign modules; the foreign
+    /// module with the longest matching path is picked.
+    pub fn mirror_item_typepath(&self, ident_path: &[String]) -> Option<Path> {
+        let source_path = path_to_strings(&self.source.fqn);
+        if ident_path.starts_with(&source_path) {
+            return Some(self.this_item_typepath(ident_path[source_path.len()..].to_vec()));
+        }
+
+        self.foreign_mods
+            .iter()
+            .map(|foreign_mod| (foreign_mod, path_to_strings(&foreign_mod.fqn)))
+            .filter(|(_, fqn)| ident_path.starts_with(fqn))
+            .max_by_key(|(_, fqn)| fqn.len())
+            .map(|(foreign_mod, fqn)| {
+                let mut path = foreign_mod.this_mod_path.clone();
+                for ident in &ident_path[fqn.len()..] {
+                    path.segments.push(syn::PathSegment {
+                        ident: Ident::new(ident.as_str(), Span::call_site()),
+                        arguments: syn::PathArguments::None,
+                    });
+                }
+                path
+            })
+    }
+
+    /// Returns the `with_validator` specs of the struct, in the order they are
+    /// specified.
+    pub fn validator_specs<'a>(
+        &'a self,
+        ident_path: &'a [String],
+    ) -> impl Iterator<Item = &'a ValidatorSpec> {
+        self.validators
+            .iter()
+            .filter(move |validator| path_to_strings(&validator.fqn) == ident_path)
+    }
+
+    /// Returns the `with_any` specs of the struct, the `ident_path` is the
+    /// struct path relative to the linked source module.
+    pub fn any_specs<'a>(&'a self, ident_path: &'a [String]) -> impl Iterator<Item = &'a AnySpec> {
+        self.anys
+            .iter()
+            .filter(move |any| path_to_strings(&any.fqn) == ident_path)
+    }
+
+    /// Returns the `with_any` spec of the struct field.
+    pub fn any_spec<'a>(
+        &'a self,
+        ident_path: &'a [String],
+        field_name: &str,
+    ) -> Option<&'a AnySpec> {
+        self.any_specs(ident_path)
+            .find(|any| any.field == field_name)
+    }
+
+    /// Returns the `with_field_type` spec of the struct field, the
+    /// `ident_path` is the struct path relative to the linked source module.
+    pub fn field_type_spec(
+        &self,
+        ident_path: &[String],
+        field_name: &str,
+    ) -> Option<&FieldTypeSpec> {
+        self.field_types.iter().find(|field_type| {
+            path_to_strings(&field_type.fqn) == ident_path && field_type.field == field_name
+        })
+    }
+
+    /// Returns the `with_newtype` spec of the struct field, the `ident_path`
+    /// is the struct path relative to the linked source module.
+    pub fn newtype_spec(&self, ident_path: &[String], field_name: &str) -> Option<&NewtypeSpec> {
+        self.newtypes.iter().find(|newtype| {
+            path_to_strings(&newtype.fqn) == ident_path && newtype.field == field_name
+        })
+    }
+
+    /// Returns the `with_flatten` spec of the struct field, the `ident_path`
+    /// is the struct path relative to the linked source module.
+    pub fn flatten_spec(&self, ident_path: &[String], field_name: &str) -> Option<&FlattenSpec> {
+        self.flattens.iter().find(|flatten| {
+            path_to_strings(&flatten.fqn) == ident_path && flatten.field == field_name
+        })
+    }
+
+    /// Returns the struct fields specified with `with_flatten`.
+    pub fn flatten_specs<'a>(
+        &'a self,
+        ident_path: &'a [String],
+    ) -> impl Iterator<Item = &'a FlattenSpec> + 'a {
+        self.flattens
+            .iter()
+            .filter(move |flatten| path_to_strings(&flatten.fqn) == ident_path)
+    }
+
+    /// Returns the field ident, if the struct field is specified with
+    /// `with_optional`; the `ident_path` is the struct path relative to the
+    /// linked source module.
+    pub fn optional_field<'a>(
+        &'a self,
+        ident_path: &'a [String],
+        field_name: &str,
+    ) -> Option<&'a Ident> {
+        self.optional_fields(ident_path)
+            .find(|field| *field == field_name)
+    }
+
+    /// Returns the struct fields specified with `with_optional`.
+    pub fn optional_fields<'a>(
+        &'a self,
+        ident_path: &'a [String],
+    ) -> impl Iterator<Item = &'a Ident> + 'a {
+        self.optionals
+            .iter()
+            .filter(move |optional| path_to_strings(&optional.fqn) == ident_path)
+            .flat_map(|optional| optional.fields.iter())
+    }
+
+    /// Returns the struct path, if the struct is specified with
+    /// `with_oneof_enum`; the `ident_path` is the struct path relative to the
+    /// linked source module.
+    pub fn oneof_enum_spec(&self, ident_path: &[String]) -> Option<&Path> {
+        self.oneof_enums
+            .iter()
+            .find(|fqn| path_to_strings(fqn) == ident_path)
+    }
+
+    /// Returns the new name of the item, if it is specified with `with_rename`;
+    /// the `ident_path` is the item path relative to the linked source module.
+    pub fn renamed_ident<S: AsRef<str>>(&self, ident_path: &[S]) -> Option<&Ident> {
+        self.renames
+            .iter()
+            .find(|rename| {
+                let item_path = path_to_strings(&rename.fqn);
+                item_path.len() == ident_path.len()
+                    && item_path
+                        .iter()
+                        .zip(ident_path)
+                        .all(|(item_ident, ident)| item_ident == ident.as_ref())
+            })
+            .map(|rename| &rename.ident)
+    }
+
+    /// Returns the struct path, if the struct is specified with
+    /// `with_transparent`; the `ident_path` is the struct path relative to the
+    /// linked source module.
+    pub fn transparent_spec(&self, ident_path: &[String]) -> Option<&Path> {
+        self.transparents
+            .iter()
+            .find(|fqn| path_to_strings(fqn) == ident_path)
+    }
+
+    /// Returns the struct path, if the struct is specified with
+    /// `with_private_fields`; the `ident_path` is the struct path relative to
+    /// the linked source module.
+    pub fn private_spec(&self, ident_path: &[String]) -> Option<&Path> {
+        self.privates
+            .iter()
+            .find(|fqn| path_to_strings(fqn) == ident_path)
+    }
+
+    /// Returns the struct path, if the struct is specified with
+    /// `with_builder`; the `ident_path` is the struct path relative to the
+    /// linked source module.
+    pub fn builder_spec(&self, ident_path: &[String]) -> Option<&Path> {
+        self.builders
+            .iter()
+            .find(|fqn| path_to_strings(fqn) == ident_path)
+    }
+
+    /// Returns the validation rules of the struct field, read with
+    /// `with_validate_rules`.
+    pub fn field_rules(&self, ident_path: &[String], field_name: &str) -> Vec<&Rule> {
+        self.validate_rules
+            .iter()
+            .flat_map(|proto_rules| proto_rules.fields.iter())
+            .filter(|field_rules| field_rules.is_field(ident_path, field_name))
+            .flat_map(|field_rules| field_rules.rules.iter())
+            .collect()
+    }
+
+    /// Returns `true` if the struct field is marked as `required` by the
+    /// validation rules.
+    pub fn is_required_by_rules(&self, ident_path: &[String], field_name: &str) -> bool {
+        self.field_rules(ident_path, field_name)
+            .contains(&&Rule::Required)
+    }
+
+    /// Returns the `with_type_mapping` spec of the original type; the type path
+    /// is either relative to `with_original_mod` argument, or is the absolute
+    /// path of prost `extern_path` type.
+    pub fn type_mapping_spec(
+        &self,
+        ident_path: &[String],
+        absolute: bool,
+    ) -> Option<&TypeMappingSpec> {
+        self.type_mappings.iter().find(|type_mapping| {
+            let orig_path = &type_mapping.orig_path;
+            let is_absolute = orig_path.leading_colon.is_some()
+                || orig_path.segments.first().unwrap().ident == "crate";
+            is_absolute == absolute && path_to_strings(orig_path) == ident_path
+        })
+    }
+
+    /// Aborts if the `with_rename` item is missing from the linked source
+    /// code, or its new name conflicts with another item.
+    fn validate_renames(&self) {
+        for rename in &self.renames {
+            let item_path = path_to_strings(&rename.fqn);
+            if !matches!(
+                crate::traverse::find_item(&self.source.ast.items, &item_path),
+                Some(Item::Struct(_) | Item::Enum(_))
+            ) {
+                abort!(
+                    rename.fqn,
+                    format!(
+                        "Struct or enum `{}` not found in the linked source code",
+                        item_path.join("::")
+                    )
+                );
+            }
+            let mut renamed_path = item_path;
+            *renamed_path.last_mut().unwrap() = rename.ident.to_string();
+            let conflicts_with_renamed = self.renames.iter().any(|other_rename| {
+                other_rename.fqn != rename.fqn
+                    && path_to_strings(&other_rename.fqn)[..renamed_path.len() - 1]
+                        == renamed_path[..renamed_path.len() - 1]
+                    && other_rename.ident == rename.ident
+            });
+            let conflicts_with_source =
+                crate::traverse::find_item(&self.source.ast.items, &renamed_path).is_some()
+                    && self.renamed_ident(&renamed_path).is_none();
+            if conflicts_with_renamed || conflicts_with_source {
+                abort!(
+                    rename.ident,
+                    format!(
+                        "Renamed item conflicts with another item: {}",
+                        renamed_path.join("::")
+                    )
+                );
+            }
+        }
+    }
+
+    /// Aborts if the `with_field_type` field is missing from the linked source
+    /// code, or the `with_type_mapping` type of the linked source module is
+    /// not its struct or enum; the types of the other modules and the prost
+    /// `extern_path` types are not checked.
+    fn validate_custom_types(&self) {
+        for field_type in &self.field_types {
+            let struct_path = path_to_strings(&field_type.fqn);
+            let Some(Item::Struct(item_struct)) =
+                crate::traverse::find_item(&self.source.ast.items, &struct_path)
+            else {
+                abort!(
+                    field_type.fqn,
+                    format!(
+                        "Struct `{}` not found in the linked source code",
+                        struct_path.join("::")
+                    )
+                );
+            };
+            if !item_struct
+                .fields
+                .iter()
+                .any(|orig_field| orig_field.ident.as_ref() == Some(&field_type.field))
+            {
+                abort!(
+                    field_type.field,
+                    format!("Field missing from struct definition: {}", field_type.field)
+                );
+            }
+        }
+
+        let source_path = path_to_strings(&self.source.fqn);
+        for type_mapping in &self.type_mappings {
+            let orig_path = &type_mapping.orig_path;
+            if orig_path.leading_colon.is_some()
+                || orig_path.segments.first().unwrap().ident == "crate"
+            {
+                continue;
+            }
+            let type_path = path_to_strings(orig_path);
+            let Some(item_path) = type_path.strip_prefix(source_path.as_slice()) else {
+                continue;
+            };
+            if !matches!(
+                crate::traverse::find_item(&self.source.ast.items, item_path),
+                Some(Item::Struct(_) | Item::Enum(_))
+            ) {
+                abort!(
+                    orig_path,
+                    format!(
+                        "Struct or enum `{}` not found in the linked source code",
+                        type_path.join("::")
+                    )
+                );
+            }
+        }
+    }
+
+    /// Adds the optional message fields of the struct to the struct spec with
+    /// the `*` wildcard, except the opted out ones; the explicitly listed
+    /// fields keep their modifiers and defaults.
+    fn expand_wildcard(&mut self, wildcard: &WildcardSpec) {
+        let struct_path = path_to_strings(&wildcard.fqn);
+        let Some(Item::Struct(item_struct)) =
+            crate::traverse::find_item(&self.source.ast.items, &struct_path)
+        else {
+            abort!(
+                wildcard.fqn,
+                format!(
+                    "Struct `{}` not found in the linked source code",
+                    struct_path.join("::")
+                )
+            );
+        };
+        for field in &wildcard.excluded {
+            if !item_struct
+                .fields
+                .iter()
+                .any(|orig_field| orig_field.ident.as_ref() == Some(field))
+            {
+                abort!(
+                    field,
+                    format!("Field missing from struct definition: {}", field)
+                );
+            }
+        }
+        let mut leaf_path = struct_path.clone();
+        if let Some(view) = &wildcard.view {
+            *leaf_path.last_mut().unwrap() = view.to_string();
+        }
+        let Some(SpecTreeLeaf::Struct(struct_spec)) = self.spec_tree.get_leaf(leaf_path.iter())
+        else {
+            unreachable!("Expected wildcard struct to be specified");
+        };
+        // the skipped fields are not required
+        let fields: Vec<Ident> = crate::traverse::message_fields(self, item_struct, &struct_path)
+            .into_iter()
+            .filter(|field| !wildcard.excluded.contains(field))
+            .filter(|field| {
+                item_struct
+                    .fields
+                    .iter()
+                    .find(|orig_field| orig_field.ident.as_ref() == Some(field))
+                    .is_some_and(|orig_field| struct_spec.skip(orig_field).is_none())
+            })
+            .cloned()
+            .collect();
+
+        match self.spec_tree.get_leaf_mut(leaf_path.iter()) {
+            Some(SpecTreeLeaf::Struct(struct_spec)) => {
+                for field in fields {
+                    struct_spec.add_field(field);
+                }
+            }
+            _ => unreachable!("Expected wildcard struct to be specified"),
+        }
+    }
+
+    /// Specifies every struct of the linked source code, that is not
+    /// specified explicitly, with its optional message fields required.
+    fn apply_default_policy(&mut self, ident_stack: &mut Vec<String>, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Mod(item_mod) => {
+                    if let Some((_, ref mod_items)) = item_mod.content {
+                        ident_stack.push(item_mod.ident.to_string());
+                        self.apply_default_policy(ident_stack, mod_items);
+                        ident_stack.pop();
+                    }
+                }
+                Item::Struct(item_struct) => {
+                    ident_stack.push(item_struct.ident.to_string());
+                    if self.spec_tree.get_leaf(ident_stack.iter()).is_none()
+                        && self.oneof_enum_spec(ident_stack).is_none()
+                    {
+                        let fields: Vec<Ident> =
+                            crate::traverse::message_fields(self, item_struct, ident_stack)
+                                .into_iter()
+                                .cloned()
+                                .collect();
+                        if !fields.is_empty() {
+                            let fqn: Path = syn::parse_str(&ident_stack.join("::"))
+                                .expect("Expected struct path to be valid");
+                            self.spec_tree
+                                .push(SpecTreeLeaf::new_struct_spec_with_modifiers(
+                                    fqn,
+                                    None,
+                                    fields,
+                                    HashMap::new(),
+                                    HashMap::new(),
+                                    HashMap::new(),
+                                    FieldSkips::default(),
+                                ));
+                        }
+                    }
+                    ident_stack.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Expands the nested field paths of the struct spec, e.g.
+    /// `order.customer`, into the named views of the field structs, e.g.
+    /// `CreateOrderOrder` view of `Order` with `customer` field listed; the
+    /// struct spec field is mirrored as the view.
+    fn expand_nested_fields(&mut self, fqn: &Path, view: Option<&Ident>, paths: &[Vec<Ident>]) {
+        let struct_path = path_to_strings(fqn);
+        let owner_ident = view
+            .cloned()
+            .unwrap_or_else(|| fqn.segments.last().unwrap().ident.clone());
+        let mut leaf_path = struct_path.clone();
+        *leaf_path.last_mut().unwrap() = owner_ident.to_string();
+
+        let mut fields: Vec<&Ident> = Vec::new();
+        for path in paths {
+            if !fields.contains(&&path[0]) {
+                fields.push(&path[0]);
+            }
+        }
+
+        for field in fields {
+            let field_struct_path = self.nested_field_struct_path(&struct_path, field);
+            let field_view = format_ident!(
+                "{}{}",
+                owner_ident,
+                field.to_string().to_upper_camel_case(),
+                span = field.span()
+            );
+            let sub_paths: Vec<Vec<Ident>> = paths
+                .iter()
+                .filter(|path| path[0] == *field)
+                .map(|path| path[1..].to_vec())
+                .collect();
+            let sub_fields: Vec<Ident> = sub_paths
+                .iter()
+                .filter(|path| path.len() == 1)
+                .map(|path| path[0].clone())
+                .collect();
+            let nested_paths: Vec<Vec<Ident>> = sub_paths
+                .into_iter()
+                .filter(|path| path.len() > 1)
+                .collect();
+
+            match self.spec_tree.get_leaf_mut(leaf_path.iter()) {
+                Some(SpecTreeLeaf::Struct(struct_spec)) => {
+                    if struct_spec.field_view(&field.to_string()).is_some() {
+                        abort!(
+                            field,
+                            "Field with nested fields can not be listed with the view name"
+                        );
+                    }
+                    struct_spec.set_field_view(field.to_string(), field_view.clone());
+                }
+                _ => unreachable!("Expected nested fields owner to be specified"),
+            }
+
+            // the view extends the struct spec of the field struct, if any,
+            // so the required fields, modifiers and defaults are kept
+            let field_fqn: Path = syn::parse_str(&field_struct_path.join("::"))
+                .expect("Expected struct path to be valid");
+            let view_leaf = match self.spec_tree.get_leaf(field_struct_path.iter()) {
+                Some(SpecTreeLeaf::Struct(base_spec)) => SpecTreeLeaf::Struct(Box::new(
+                    base_spec.to_view(field_view.clone(), sub_fields),
+                )),
+                _ => SpecTreeLeaf::new_struct_spec_with_modifiers(
+                    field_fqn.clone(),
+                    Some(field_view.clone()),
+                    sub_fields,
+                    HashMap::new(),
+                    HashMap::new(),
+                    HashMap::new(),
+                    FieldSkips::default(),
+                ),
+            };
+            self.spec_tree.push(view_leaf);
+            if !nested_paths.is_empty() {
+                self.expand_nested_fields(&field_fqn, Some(&field_view), &nested_paths);
+            }
+        }
+    }
+
+    /// Returns the path of the field struct type, relative to the linked
+    /// source module; aborts if the field is not of the struct type.
+    fn nested_field_struct_path(&self, struct_path: &[String], field: &Ident) -> Vec<String> {
+        let Some(Item::Struct(item_struct)) =
+            crate::traverse::find_item(&self.source.ast.items, struct_path)
+        else {
+            abort!(
+                field,
+                format!(
+                    "Struct `{}` not found in the linked source code",
+                    struct_path.join("::")
+                )
+            );
+        };
+        let Some(orig_field) = item_struct
+            .fields
+            .iter()
+            .find(|orig_field| orig_field.ident.as_ref() == Some(field))
+        else {
+            abort!(
+                field,
+                format!("Field missing from struct definition: {}", field)
+            );
+        };
+
+        let (_, element_ty) = crate::traverse::field_shape(&orig_field.ty);
+        let field_struct_path = match element_ty {
+            Type::Path(ty_path) => crate::traverse::source_item_path(self, ty_path, struct_path),
+            _ => None,
+        }
+        .filter(|path| {
+            matches!(
+                crate::traverse::find_item(&self.source.ast.items, path),
+                Some(Item::Struct(_))
+            )
+        });
+        match field_struct_path {
+            Some(field_struct_path) => field_struct_path,
+            None => {
+                let ty = &orig_field.ty;
+                abort!(
+                    field,
+                    format!(
+                        "Field has type `{}`, which is not a struct of the linked source code",
+                        quote!(#ty)
+                    )
+                );
+            }
+        }
+    }
+
+    pub fn orig_item_typepath<I: IntoIterator<Item = String>>(&self, ident_path: I) -> Path {
+        let absolute_part = self.orig_mod_path.clone();
+        self.item_typepath(absolute_part, ident_path)
+    }
+
+    pub fn this_item_typepath<I: IntoIterator<Item = String>>(&self, ident_path: I) -> Path {
+        let absolute_part = self.this_mod_path.clone();
+        // the renamed items are mirrored under their new names
+        let mut ident_path: Vec<String> = ident_path.into_iter().collect();
+        if let Some(ident) = self.renamed_ident(&ident_path) {
+            *ident_path.last_mut().unwrap() = ident.to_string();
+        }
+        self.item_typepath(absolute_part, ident_path)
+    }
+
+    fn item_typepath<I: IntoIterator<Item = String>>(
+        &self,
+        mut absolute_part: Path,
+        ident_path: I,
+    ) -> Path {
+        for ident in self.source.fqn.segments.iter() {
+            absolute_part.segments.push(ident.clone());
+        }
+        for ident in ident_path {
+            absolute_part.segments.push(syn::PathSegment {
+                ident: Ident::new(ident.as_str(), Span::call_site()),
+                arguments: syn::PathArguments::None,
+            });
+        }
+        absolute_part
+    }
+}
+
+fn path_to_strings(path: &Path) -> Vec<String> {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect()
+}
+
+/// The mirror module of prost `extern_path` items, e.g. the items compiled and
+/// mirrored by another crate.
+#[derive(Clone, Debug)]
+pub(crate) struct Extern {
+    /// Absolute path of the original items, as specified in prost
+    /// `extern_path`, e.g. `::common_proto::generated`
+    pub orig_mod_path: Path,
+    /// Absolute path of the mirrored items, e.g. `::common_proto::unwrapped`
+    pub this_mod_path: Path,
+}
+
+/// The `prost_types::Any` field, decoded into one of the listed message types.
+#[derive(Clone, Debug)]
+pub(crate) struct AnySpec {
+    /// Struct path relative to the linked source module, e.g. `root::Envelope`
+    pub fqn: Path,
+    /// The `prost_types::Any` field of the struct
+    pub field: Ident,
+    /// Message types paths relative to the linked source module
+    pub types: Vec<Path>,
+}
+
+impl AnySpec {
+    /// Returns the mirror enum ident, e.g. `EnvelopePayload` for
+    /// `Envelope.payload` field.
+    pub fn enum_ident(&self) -> Ident {
+        let struct_ident = &self.fqn.segments.last().unwrap().ident;
+        format_ident!(
+            "{}{}",
+            struct_ident,
+            self.field.unraw().to_string().to_upper_camel_case()
+        )
+    }
+}
+
+/// The custom mirror type and the conversion functions.
+#[derive(Clone, Debug)]
+pub(crate) struct CustomType {
+    /// The mirror type, e.g. `uuid::Uuid`
+    pub ty: Type,
+    /// `fn(Original) -> Result<Mirrored, E>`, `E` implements `std::error::Error`
+    pub try_from_fn: Expr,
+    /// `fn(&Mirrored) -> Original`
+    pub into_fn: Expr,
+}
+
+/// The field of the custom mirror type, specified with `with_field_type`.
+#[derive(Clone, Debug)]
+pub(crate) struct FieldTypeSpec {
+    /// Struct path relative to the linked source module, e.g. `root::Order`
+    pub fqn: Path,
+    pub field: Ident,
+    pub custom_type: CustomType,
+}
+
+/// The original type mapped into the custom mirror type in all fields,
+/// specified with `with_type_mapping`.
+#[derive(Clone, Debug)]
+pub(crate) struct TypeMappingSpec {
+    /// Type path relative to `with_original_mod` argument, e.g. `common::Money`,
+    /// or the absolute path of prost `extern_path` type
+    pub orig_path: Path,
+    pub custom_type: CustomType,
+}
+
+/// The field wrapped into the newtype, specified with `with_newtype`; the same
+/// newtype may be used by multiple fields.
+#[derive(Clone, Debug)]
+pub(crate) struct NewtypeSpec {
+    /// Struct path relative to the linked source module, e.g. `root::User`
+    pub fqn: Path,
+    pub field: Ident,
+    /// The newtype ident, the newtype is placed into the mirror module
+    pub ident: Ident,
+}
+
+impl NewtypeSpec {
+    /// Returns the struct path and the field name, e.g. `root::User.id`.
+    pub fn field_path(&self) -> String {
+        format!("{}.{}", path_to_strings(&self.fqn).join("::"), self.field)
+    }
+}
+
+/// The struct fields, which default values are represented as `None`,
+/// specified with `with_optional`.
+#[derive(Clone, Debug)]
+pub(crate) struct OptionalSpec {
+    /// Struct path relative to the linked source module, e.g. `root::User`
+    pub fqn: Path,
+    pub fields: Vec<Ident>,
+}
+
+/// The nested field paths of the struct spec, e.g. `order.customer`, expanded
+/// into the named views once the linked source code is loaded.
+#[derive(Clone, Debug)]
+pub(crate) struct NestedFieldsSpec {
+    /// Struct path relative to the linked source module, e.g. `root::CreateOrder`
+    pub fqn: Path,
+    /// The named view ident, if the struct spec is the named view
+    pub view: Option<Ident>,
+    /// The field paths, each one has at least 2 fields
+    pub paths: Vec<Vec<Ident>>,
+}
+
+/// The `*` wildcard of the struct spec fields list, expanded into the optional
+/// message fields once the linked source code is loaded.
+#[derive(Clone, Debug)]
+pub(crate) struct WildcardSpec {
+    /// Struct path relative to the linked source module, e.g. `root::Msg`
+    pub fqn: Path,
+    /// The named view ident, if the struct spec is the named view
+    pub view: Option<Ident>,
+    /// The fields opted out of the wildcard, e.g. `!field1`
+    pub excluded: Vec<Ident>,
+}
+
+/// The struct or enum mirrored under another name, specified with
+/// `with_rename`.
+#[derive(Clone, Debug)]
+pub(crate) struct RenameSpec {
+    /// Item path relative to the linked source module, e.g. `root::PbUser`
+    pub fqn: Path,
+    /// The mirror item ident, e.g. `User`
+    pub ident: Ident,
+}
+
+/// The required message field, which fields are inlined into the mirror
+/// struct, specified with `with_flatten`.
+#[derive(Clone, Debug)]
+pub(crate) struct FlattenSpec {
+    /// Struct path relative to the linked source module, e.g. `root::Person`
+    pub fqn: Path,
+    pub field: Ident,
+    /// The prefix of the inlined fields names, e.g. `name_`
+    pub prefix: String,
+}
+
+/// The struct validation function, specified with `with_validator`.
+#[derive(Clone, Debug)]
+pub(crate) struct ValidatorSpec {
+    /// Struct path relative to the linked source module, e.g. `root::Booking`
+    pub fqn: Path,
+    /// Function path, e.g. `crate::validate::booking`
+    pub validator: Path,
+    /// The function accepts the original struct instead of the mirror one
+    pub original: bool,
+}
+
+/// Contains config structures and functions to collect and operate on a tree
+/// of specificated structs and enums.
+pub(crate) mod spec_tree {
+    use std::collections::hash_map::Entry;
+    use std::collections::HashMap;
+
+    use proc_macro2::Span;
+    use proc_macro_error::abort;
+    use syn::punctuated::Punctuated;
+    use syn::Expr;
+    use syn::Field;
+    use syn::Ident;
+    use syn::Path;
+    use syn::PathSegment;
+
+    #[derive(Clone, Debug)]
+    pub(crate) struct StructSpec {
+        fqn: Path,
+        /// The named view ident, e.g. `UserForCreate` for
+        /// `User as UserForCreate`; the view is mirrored next to the struct
+        view: Option<Ident>,
+        fields: Vec<Ident>,
+        modifiers: HashMap<String, FieldModifier>,
+        defaults: HashMap<String, FieldDefault>,
+        /// The fields of the named view type, e.g. `user as UserForCreate`
+        field_views: HashMap<String, Ident>,
+        /// The fields excluded from the mirror struct, e.g.
+        /// `skip = [debug_info]`
+        skips: FieldSkips,
+        /// The mirror fields names, e.g. `rename = { pb_user_id => user_id }`
+        field_renames: Vec<(Ident, Ident)>,
+    }
+
+    /// The way the field excluded from the mirror struct is converted.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub(crate) enum FieldSkip {
+        /// The field value is discarded
+        Discard,
+        /// The field value must be the default one, e.g.
+        /// `debug_info = reject`
+        Reject,
+    }
+
+    /// The fields excluded from the mirror struct.
+    #[derive(Clone, Debug, Default)]
+    pub(crate) struct FieldSkips {
+        pub fields: Vec<(Ident, FieldSkip)>,
+        /// The `#[deprecated]` fields, e.g. `deprecated()`
+        pub deprecated: Option<FieldSkip>,
+    }
+
+    /// The value of the required field, if it is missing, e.g.
+    /// `field1 = default`.
+    #[derive(Clone, Debug)]
+    pub(crate) enum FieldDefault {
+        /// The `Default` value of the original field type
+        Default,
+        /// The function call, returning the original field type
+        Call(Expr),
+    }
+
+    /// The requirement for the non-`Option<T>` field, e.g. `field1 = non_empty`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub(crate) enum FieldModifier {
+        /// String, repeated or map field must not be empty
+        NonEmpty,
+        /// Repeated field must not be empty, the mirror field type is
+        /// `prost_unwrap::NonEmpty<Vec<T>>`
+        NonEmptyVec,
+        /// Scalar number or enum field must not be zero
+        NonZero,
+        /// Oneof field must hold the variant, the mirror field type is the
+        /// variant inner type
+        Variant(Ident),
+    }
+
+    impl FieldModifier {
+        pub fn name(&self) -> &'static str {
+            match self {
+                FieldModifier::NonEmpty => "non_empty",
+                FieldModifier::NonEmptyVec => "non_empty_vec",
+                FieldModifier::NonZero => "non_zero",
+                FieldModifier::Variant(_) => "variant",
+            }
+        }
+    }
+
+    impl StructSpec {
+        pub fn fields_map(&self) -> HashMap<String, &Ident> {
+            let mut hashmap = HashMap::new();
+            for field in self.fields.iter() {
+                hashmap.insert(field.to_string(), field);
+            }
+            hashmap
+        }
+
+        /// Returns the modifier of the field, if any; the listed fields
+        /// without modifier are required `Option<T>` fields.
+        pub fn modifier(&self, field_name: &str) -> Option<FieldModifier> {
+            self.modifiers.get(field_name).cloned()
+        }
+
+        /// Returns the default value of the required field, if any.
+        pub fn default(&self, field_name: &str) -> Option<&FieldDefault> {
+            self.defaults.get(field_name)
+        }
+
+        pub fn fqn(&self) -> &Path {
+            &self.fqn
+        }
+
+        /// Returns the named view ident, if the spec is the named view.
+        pub fn view(&self) -> Option<&Ident> {
+            self.view.as_ref()
+        }
+
+        /// Returns the named view ident of the field type, if any.
+        pub fn field_view(&self, field_name: &str) -> Option<&Ident> {
+            self.field_views.get(field_name)
+        }
+
+        /// Returns the way the field is excluded from the mirror struct, if
+        /// it is; the listed fields are not excluded with the `#[deprecated]`
+        /// fields.
+        pub fn skip(&self, field: &Field) -> Option<FieldSkip> {
+            let field_ident = field.ident.as_ref()?;
+            self.skips
+                .fields
+                .iter()
+                .find(|(skipped_field, _)| skipped_field == field_ident)
+                .map(|(_, skip)| *skip)
+                .or_else(|| {
+                    self.skips.deprecated.filter(|_| {
+                        !self.fields.contains(field_ident)
+                            && field
+                                .attrs
+                                .iter()
+                                .any(|attr| attr.path().is_ident("deprecated"))
+                    })
+                })
+        }
+
+        /// Returns the fields excluded from the mirror struct by name.
+        pub fn skipped_fields(&self) -> impl Iterator<Item = &Ident> {
+            self.skips.fields.iter().map(|(field, _)| field)
+        }
+
+        /// Returns the mirror field name, if the field is renamed.
+        pub fn field_rename(&self, field_name: &str) -> Option<&Ident> {
+            self.field_renames
+                .iter()
+                .find(|(field, _)| field == field_name)
+                .map(|(_, renamed)| renamed)
+        }
+
+        /// Returns the renamed fields with their mirror names.
+        pub fn field_renames(&self) -> &[(Ident, Ident)] {
+            &self.field_renames
+        }
+
+        /// Sets the mirror fields names.
+        pub fn set_field_renames(&mut self, field_renames: Vec<(Ident, Ident)>) {
+            self.field_renames = field_renames;
+        }
+
+        /// Adds the required field, unless it is listed already.
+        pub fn add_field(&mut self, field: Ident) {
+            if !self.fields.contains(&field) {
+                self.fields.push(field);
+            }
+        }
+
+        /// Returns the named view of the struct, with the fields of the view
+        /// required in addition to the ones of the struct spec.
+        pub fn to_view(&self, view: Ident, fields: Vec<Ident>) -> Self {
+            let mut view_spec = self.clone();
+            view_spec.view = Some(view);
+            for field in fields {
+                view_spec.add_field(field);
+            }
+            view_spec
+        }
+
+        /// Sets the named view ident of the field type.
+        pub fn set_field_view(&mut self, field_name: String, view: Ident) {
+            self.field_views.insert(field_name, view);
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub(crate) struct EnumSpec {
+        fqn: Path,
+    }
+
+    #[derive(Clone, Debug)]
+    #[allow(dead_code)]
+    pub(crate) enum SpecTreeLeaf {
+        Struct(Box<StructSpec>),
         Enum(EnumSpec),
     }
 
     impl SpecTreeLeaf {
+        #[cfg(test)]
         pub fn new_struct_spec(fqn: Path, fields: Vec<Ident>) -> Self {
-            SpecTreeLeaf::Struct(StructSpec { fqn, fields })
+            Self::new_struct_spec_with_modifiers(
+                fqn,
+                None,
+                fields,
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                FieldSkips::default(),
+            )
+        }
+
+        pub fn new_struct_spec_with_modifiers(
+            fqn: Path,
+            view: Option<Ident>,
+            fields: Vec<Ident>,
+            modifiers: HashMap<String, FieldModifier>,
+            defaults: HashMap<String, FieldDefault>,
+            field_views: HashMap<String, Ident>,
+            skips: FieldSkips,
+        ) -> Self {
+            SpecTreeLeaf::Struct(Box::new(StructSpec {
+                fqn,
+                view,
+                fields,
+                modifiers,
+                defaults,
+                field_views,
+                skips,
+                field_renames: Vec::new(),
+            }))
         }
 
         pub fn new_enum_spec(fqn: Path) -> Self {
@@ -539,7 +3053,7 @@ pub(crate) mod spec_tree {
 
         pub fn fqn_ref(&self) -> &Path {
             match self {
-                SpecTreeLeaf::Struct(StructSpec { ref fqn, .. }) => fqn,
+                SpecTreeLeaf::Struct(struct_spec) => &struct_spec.fqn,
                 SpecTreeLeaf::Enum(EnumSpec { ref fqn, .. }) => fqn,
             }
         }
@@ -603,7 +3117,8 @@ pub(crate) mod spec_tree {
         /// Pushes the SpecTreeNode into SpecTree, creating necessary module
         /// specs along the path; path is fully-qualified struct or enum name,
         /// specified from the root module (see `with_orig_mod` argument), e.g.
-        /// `root::child::StructA`
+        /// `root::child::StructA`; the named view is pushed next to the
+        /// struct under the view name.
         pub fn push(&mut self, spec_tree_leaf: SpecTreeLeaf) -> &mut Self {
             let mut path_vec: Vec<_> = spec_tree_leaf
                 .fqn_ref()
@@ -611,7 +3126,12 @@ pub(crate) mod spec_tree {
                 .iter()
                 .map(|segment| segment.ident.to_string())
                 .collect();
-            let leaf_name = path_vec.pop().expect("Expected leaf fqn to be non-empty");
+            let mut leaf_name = path_vec.pop().expect("Expected leaf fqn to be non-empty");
+            if let SpecTreeLeaf::Struct(struct_spec) = &spec_tree_leaf {
+                if let Some(view) = &struct_spec.view {
+                    leaf_name = view.to_string();
+                }
+            }
             let path = path_vec.into_iter().peekable();
 
             let mut current_node = &mut self.inner;
@@ -651,25 +3171,61 @@ pub(crate) mod spec_tree {
             current_node.leafs.get(leaf_name)
         }
 
-        // pub fn get_leaf_mut<'tree, 'path, 'leaf>(
-        //     &'tree mut self,
-        //     path: impl IntoIterator<Item = &'path String>,
-        // ) -> Option<&'leaf mut SpecTreeLeaf>
-        // where
-        //     'tree: 'leaf,
-        // {
-        //     let mut path_vec: Vec<_> = path.into_iter().collect();
-        //     let leaf_name = path_vec.pop()?;
-        //     let mut path = path_vec.into_iter().peekable();
+        /// Returns the named views specs of the struct, sorted by the view
+        /// name.
+        pub fn get_views<'tree, 'path>(
+            &'tree self,
+            path: impl IntoIterator<Item = &'path String>,
+        ) -> Vec<&'tree StructSpec> {
+            let mut path_vec: Vec<_> = path.into_iter().collect();
+            let Some(struct_name) = path_vec.pop() else {
+                return Vec::new();
+            };
+
+            let mut current_node = &self.inner;
+            for ident in path_vec {
+                let Some(node) = current_node.nodes.get(ident) else {
+                    return Vec::new();
+                };
+                current_node = node;
+            }
+
+            let mut views: Vec<_> = current_node
+                .leafs
+                .values()
+                .filter_map(|leaf| match leaf {
+                    SpecTreeLeaf::Struct(struct_spec)
+                        if struct_spec.view.is_some()
+                            && struct_spec.fqn.segments.last().unwrap().ident == struct_name =>
+                    {
+                        Some(struct_spec.as_ref())
+                    }
+                    _ => None,
+                })
+                .collect();
+            views.sort_by_key(|struct_spec| struct_spec.view.as_ref().unwrap().to_string());
+            views
+        }
+
+        pub fn get_leaf_mut<'tree, 'path, 'leaf>(
+            &'tree mut self,
+            path: impl IntoIterator<Item = &'path String>,
+        ) -> Option<&'leaf mut SpecTreeLeaf>
+        where
+            'tree: 'leaf,
+        {
+            let mut path_vec: Vec<_> = path.into_iter().collect();
+            let leaf_name = path_vec.pop()?;
+            let path = path_vec.into_iter();
 
-        //     let mut current_node = &mut self.inner;
+            let mut current_node = &mut self.inner;
 
-        //     while let Some(ident) = path.next() {
-        //         current_node = current_node.nodes.get_mut(ident)?;
-        //     }
+            for ident in path {
+                current_node = current_node.nodes.get_mut(ident)?;
+            }
 
-        //     current_node.leafs.get_mut(leaf_name)
-        // }
+            current_node.leafs.get_mut(leaf_name)
+        }
 
         // pub fn contains_leaf<'path, I: IntoIterator<Item = &'path String>>(&self, path: I) -> bool {
         //     self.get_leaf(path).is_some()
@@ -758,6 +3314,31 @@ pub(crate) mod spec_tree {
                 .is_some());
         }
 
+        #[test]
+        fn push_views() {
+            let mut tree = SpecTree::new();
+            tree.push(SpecTreeLeaf::new_struct_spec(
+                parse_str("root::StructA").unwrap(),
+                Vec::new(),
+            ))
+            .push(SpecTreeLeaf::new_struct_spec_with_modifiers(
+                parse_str("root::StructA").unwrap(),
+                Some(parse_str("StructAView").unwrap()),
+                Vec::new(),
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                FieldSkips::default(),
+            ));
+
+            let path = vec!["root".to_string(), "StructA".to_string()];
+            assert!(tree.get_leaf(&path).is_some());
+
+            let views = tree.get_views(&path);
+            assert_eq!(views.len(), 1);
+            assert_eq!(views[0].view().unwrap(), "StructAView");
+        }
+
         #[test]
         fn get_non_existent() {
             let tree = SpecTree::new();
@@ -1,5 +1,6 @@
 mod include;
 mod traverse;
+mod validate_rules;
 
 use include::Config;
 use proc_macro2::TokenStream;
@@ -0,0 +1,239 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::LitStr;
+use syn::Type;
+
+use super::FieldShape;
+use crate::include::Config;
+use crate::validate_rules::Rule;
+
+const CHECK_REQUIRED: &str = r#"
+    if {field_value} == <{field_ty} as Default>::default() {{
+        return Err({error_typepath}::required("{field_name}"));
+    }}
+"#;
+const CHECK_RULES_PLAIN: &str = r#"
+    {{
+        let field_value = &{field_value};
+        {checks}
+    }}
+"#;
+const CHECK_RULES_OPTION: &str = r#"
+    if let Some(field_value) = &{field_value} {{
+        {checks}
+    }}
+"#;
+const CHECK_RULE: &str = r#"
+    if {condition} {{
+        return Err({error_typepath}::new({reason}).at("{field_name}"));
+    }}
+"#;
+
+/// Returns the statements checking the original field value against the
+/// `with_validate_rules` rules; the statements are placed before the mirror
+/// struct is built.
+pub(crate) fn checks(
+    config: &Config,
+    ident_stack: &[String],
+    field_name: &str,
+    ty: &Type,
+) -> Vec<String> {
+    let rules = config.field_rules(ident_stack, field_name);
+    // `Option<T>` field is unwrapped instead, see `Config::is_required_by_rules`
+    let is_required =
+        rules.contains(&&Rule::Required) && super::field_shape(ty).0 != FieldShape::Option;
+    rule_checks(
+        &rules,
+        is_required,
+        ty,
+        &format!("value.{field_name}"),
+        field_name,
+        "Self::Error",
+    )
+}
+
+/// Returns the statements checking the `with_private_fields` constructor
+/// argument against the `with_validate_rules` rules of the original field;
+/// aborts if the mirror field is converted into another type, so the rules
+/// can not be checked on the argument.
+pub(crate) fn argument_checks(
+    config: &Config,
+    ident_stack: &[String],
+    field_name: &str,
+    orig_ty: &Type,
+    argument: &str,
+    argument_ty: &Type,
+    error_typepath: &str,
+) -> Vec<String> {
+    let rules = config.field_rules(ident_stack, field_name);
+    // the required `Option<T>` field is unwrapped, so the argument is set
+    let is_required =
+        rules.contains(&&Rule::Required) && super::field_shape(orig_ty).0 != FieldShape::Option;
+    let has_value_rules = rules.iter().any(|rule| **rule != Rule::Required);
+    if (is_required || has_value_rules)
+        && element_ident(super::field_shape(orig_ty).1)
+            != element_ident(super::field_shape(argument_ty).1)
+    {
+        abort!(
+            config.private_spec(ident_stack).unwrap(),
+            format!(
+                "Field `{}` with validation rules is converted into another type, the rules can not be checked by the `new` constructor",
+                field_name
+            )
+        );
+    }
+    rule_checks(
+        &rules,
+        is_required,
+        argument_ty,
+        argument,
+        argument,
+        error_typepath,
+    )
+}
+
+fn element_ident(element_ty: &Type) -> String {
+    match element_ty {
+        Type::Path(ty_path) => ty_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Returns the statements checking the `field_value` expression of the `ty`
+/// type; the failed field is reported as `field_name`.
+fn rule_checks(
+    rules: &[&Rule],
+    is_required: bool,
+    ty: &Type,
+    field_value: &str,
+    field_name: &str,
+    error_typepath: &str,
+) -> Vec<String> {
+    let (shape, element_ty) = super::field_shape(ty);
+    let element_ident = element_ident(element_ty);
+
+    let mut checks = Vec::new();
+    if is_required {
+        checks.push(
+            strfmt!(
+                CHECK_REQUIRED,
+                field_value => field_value.to_string(),
+                field_name => field_name.to_string(),
+                field_ty => quote!(#ty).to_string(),
+                error_typepath => error_typepath.to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    let rule_checks: Vec<String> = rules
+        .iter()
+        .filter_map(|rule| rule_check(rule, &element_ident))
+        .map(|(condition, reason)| {
+            let reason = LitStr::new(&reason, proc_macro2::Span::call_site());
+            strfmt!(
+                CHECK_RULE,
+                field_name => field_name.to_string(),
+                condition => condition,
+                reason => quote!(#reason).to_string(),
+                error_typepath => error_typepath.to_string()
+            )
+            .unwrap()
+        })
+        .collect();
+    if !rule_checks.is_empty() {
+        let template = match shape {
+            FieldShape::Option => CHECK_RULES_OPTION,
+            _ => CHECK_RULES_PLAIN,
+        };
+        checks.push(
+            strfmt!(
+                template,
+                field_value => field_value.to_string(),
+                checks => rule_checks.join("")
+            )
+            .unwrap(),
+        );
+    }
+
+    checks
+}
+
+/// Returns the condition failing the rule, and the error reason; the
+/// condition refers the `field_value: &T` binding.
+fn rule_check(rule: &Rule, element_ident: &str) -> Option<(String, String)> {
+    // string length is measured in characters, bytes length in bytes
+    let len = if element_ident == "String" {
+        "field_value.chars().count()"
+    } else {
+        "field_value.len()"
+    };
+    let number = |number: &str| {
+        let is_float = element_ident == "f32" || element_ident == "f64";
+        if is_float && !number.contains(['.', 'e', 'E']) {
+            format!("{number}.0")
+        } else {
+            number.to_string()
+        }
+    };
+
+    let check = match rule {
+        Rule::Required => return None,
+        Rule::MinLen(n) => (
+            format!("{len} < {n}"),
+            format!("length must be at least {n}"),
+        ),
+        Rule::MaxLen(n) => (
+            format!("{len} > {n}"),
+            format!("length must be at most {n}"),
+        ),
+        Rule::Len(n) => (format!("{len} != {n}"), format!("length must be {n}")),
+        Rule::Prefix(prefix) => (
+            format!("!field_value.starts_with({prefix:?})"),
+            format!("must start with {prefix:?}"),
+        ),
+        Rule::Suffix(suffix) => (
+            format!("!field_value.ends_with({suffix:?})"),
+            format!("must end with {suffix:?}"),
+        ),
+        Rule::Contains(substring) => (
+            format!("!field_value.contains({substring:?})"),
+            format!("must contain {substring:?}"),
+        ),
+        Rule::Gt(n) => (
+            format!("*field_value <= {}", number(n)),
+            format!("must be greater than {n}"),
+        ),
+        Rule::Gte(n) => (
+            format!("*field_value < {}", number(n)),
+            format!("must be greater than or equal to {n}"),
+        ),
+        Rule::Lt(n) => (
+            format!("*field_value >= {}", number(n)),
+            format!("must be less than {n}"),
+        ),
+        Rule::Lte(n) => (
+            format!("*field_value > {}", number(n)),
+            format!("must be less than or equal to {n}"),
+        ),
+        Rule::Const(n) => (
+            format!("*field_value != {}", number(n)),
+            format!("must be equal to {n}"),
+        ),
+        Rule::MinItems(n) => (
+            format!("field_value.len() < {n}"),
+            format!("must contain at least {n} items"),
+        ),
+        Rule::MaxItems(n) => (
+            format!("field_value.len() > {n}"),
+            format!("must contain at most {n} items"),
+        ),
+    };
+    Some(check)
+}
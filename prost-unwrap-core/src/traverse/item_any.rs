@@ -0,0 +1,205 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Fields;
+use syn::Item;
+use syn::ItemStruct;
+
+use crate::include::AnySpec;
+use crate::include::Config;
+use crate::traverse::Traverse;
+
+/// Generates the mirror enums for the `prost_types::Any` fields of the struct,
+/// specified with `with_any`.
+pub struct AnyEnum;
+
+impl Traverse for AnyEnum {
+    type Item = ItemStruct;
+
+    fn traverse(config: &Config, item: &Self::Item, ident_stack: &mut Vec<String>) -> Vec<Item> {
+        let mut vec = Vec::new();
+        for any_spec in config.any_specs(ident_stack) {
+            validate_field(item, any_spec);
+            vec.extend(generate_enum(config, any_spec));
+            vec.extend(generate_try_from_any(config, any_spec));
+            vec.extend(generate_into_any(config, any_spec));
+        }
+        vec
+    }
+}
+
+fn validate_field(item: &ItemStruct, any_spec: &AnySpec) {
+    let Fields::Named(ref fields) = item.fields else {
+        abort!(any_spec.fqn, "Expected struct to have named fields");
+    };
+
+    let Some(field) = fields
+        .named
+        .iter()
+        .find(|field| field.ident.as_ref() == Some(&any_spec.field))
+    else {
+        abort!(
+            any_spec.field,
+            format!("Field missing from struct definition: {}", any_spec.field)
+        );
+    };
+
+    let (_, element_ty) = super::field_shape(&field.ty);
+    if super::well_known_types::prost_types_ident(element_ty).as_deref() != Some("Any") {
+        let ty = &field.ty;
+        abort!(
+            any_spec.field,
+            format!(
+                "Field has type `{}`, which is not a `prost_types::Any` type",
+                quote!(#ty)
+            )
+        );
+    }
+
+    for (i, ty) in any_spec.types.iter().enumerate() {
+        let variant_ident = &ty.segments.last().unwrap().ident;
+        if any_spec.types[..i]
+            .iter()
+            .any(|other| other.segments.last().unwrap().ident == *variant_ident)
+        {
+            abort!(
+                ty,
+                format!(
+                    "Multiple message types named `{}` are not allowed",
+                    variant_ident
+                )
+            );
+        }
+    }
+}
+
+fn type_idents(ty: &syn::Path) -> Vec<String> {
+    ty.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect()
+}
+
+const ENUM_DEF_HEADER: &str = r#"
+    #[derive(Clone, PartialEq)]
+    pub enum {enum_name} {{
+"#;
+const ENUM_DEF_VARIANT: &str = "{variant_name}({mirror_typepath}),";
+const ENUM_DEF_FOOTER: &str = "}";
+
+fn generate_enum(config: &Config, any_spec: &AnySpec) -> Vec<Item> {
+    let mut enum_def = vec![strfmt!(
+        ENUM_DEF_HEADER,
+        enum_name => any_spec.enum_ident().to_string()
+    )
+    .unwrap()];
+
+    for ty in &any_spec.types {
+        let mirror_typepath = config.this_item_typepath(type_idents(ty));
+        enum_def.push(
+            strfmt!(
+                ENUM_DEF_VARIANT,
+                variant_name => ty.segments.last().unwrap().ident.to_string(),
+                mirror_typepath => quote!(#mirror_typepath).to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    enum_def.push(ENUM_DEF_FOOTER.to_string());
+    let item_enum: Item = syn::parse_str(enum_def.join("").as_str()).unwrap();
+
+    vec![item_enum]
+}
+
+const IMPL_BLOCK_TRY_FROM_ANY_HEADER: &str = r#"
+    impl std::convert::TryFrom<::prost_types::Any> for {enum_name} {{
+        type Error = {error_typepath};
+
+        fn try_from(value: ::prost_types::Any) -> Result<Self, Self::Error> {{
+            let full_name = value.type_url.rsplit('/').next().unwrap_or_default();
+"#;
+const IMPL_BLOCK_TRY_FROM_ANY_VARIANT: &str = r#"
+    if full_name == <{orig_typepath} as ::prost::Name>::full_name() {{
+        let message = <{orig_typepath} as ::prost::Message>::decode(value.value.as_slice())
+            .map_err(|e| Self::Error::nested("{variant_name}", e))?;
+        return message
+            .try_into()
+            .map(Self::{variant_name})
+            .map_err(|e| Self::Error::nested("{variant_name}", e));
+    }}
+"#;
+const IMPL_BLOCK_TRY_FROM_ANY_FOOTER: &str = r#"
+            Err(Self::Error::new(format!("unknown type url `{}`", value.type_url)))
+        }
+    }
+"#;
+
+fn generate_try_from_any(config: &Config, any_spec: &AnySpec) -> Vec<Item> {
+    let error_typepath = config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+
+    let mut try_from_impl = vec![strfmt!(
+        IMPL_BLOCK_TRY_FROM_ANY_HEADER,
+        enum_name => any_spec.enum_ident().to_string(),
+        error_typepath => quote!(#error_typepath).to_string()
+    )
+    .unwrap()];
+
+    for ty in &any_spec.types {
+        let orig_typepath = config.orig_item_typepath(type_idents(ty));
+        try_from_impl.push(
+            strfmt!(
+                IMPL_BLOCK_TRY_FROM_ANY_VARIANT,
+                variant_name => ty.segments.last().unwrap().ident.to_string(),
+                orig_typepath => quote!(#orig_typepath).to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    try_from_impl.push(IMPL_BLOCK_TRY_FROM_ANY_FOOTER.to_string());
+    let try_from_impl_block: Item = syn::parse_str(try_from_impl.join("").as_str()).unwrap();
+
+    vec![try_from_impl_block]
+}
+
+const IMPL_BLOCK_INTO_ANY_HEADER: &str = r#"
+    impl std::convert::Into<::prost_types::Any> for {enum_name} {{
+        fn into(self) -> ::prost_types::Any {{
+            match self {{
+"#;
+const IMPL_BLOCK_INTO_ANY_VARIANT: &str = r#"
+    Self::{variant_name}(message) => {{
+        let message: {orig_typepath} = message.into();
+        ::prost_types::Any {{
+            type_url: <{orig_typepath} as ::prost::Name>::type_url(),
+            value: ::prost::Message::encode_to_vec(&message),
+        }}
+    }}
+"#;
+const IMPL_BLOCK_INTO_ANY_FOOTER: &str = "}}}";
+
+fn generate_into_any(config: &Config, any_spec: &AnySpec) -> Vec<Item> {
+    let mut into_impl = vec![strfmt!(
+        IMPL_BLOCK_INTO_ANY_HEADER,
+        enum_name => any_spec.enum_ident().to_string()
+    )
+    .unwrap()];
+
+    for ty in &any_spec.types {
+        let orig_typepath = config.orig_item_typepath(type_idents(ty));
+        into_impl.push(
+            strfmt!(
+                IMPL_BLOCK_INTO_ANY_VARIANT,
+                variant_name => ty.segments.last().unwrap().ident.to_string(),
+                orig_typepath => quote!(#orig_typepath).to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    into_impl.push(IMPL_BLOCK_INTO_ANY_FOOTER.to_string());
+    let into_impl_block: Item = syn::parse_str(into_impl.join("").as_str()).unwrap();
+
+    vec![into_impl_block]
+}
@@ -0,0 +1,394 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Fields;
+use syn::Item;
+use syn::ItemStruct;
+
+use crate::include::spec_tree::SpecTreeLeaf;
+use crate::include::spec_tree::StructSpec;
+use crate::include::Config;
+
+/// The method of the builder, which must not be shadowed by the field
+/// setters.
+const METHOD_NAMES: [&str; 1] = ["build"];
+
+/// Aborts if any of the `with_builder` structs is not a struct of the linked
+/// source code, or its builder name conflicts with the linked source code
+/// item.
+pub(crate) fn validate_specs(config: &Config) {
+    for fqn in &config.builders {
+        let ident_path: Vec<String> = fqn
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if !matches!(
+            super::find_item(&config.source.ast.items, &ident_path),
+            Some(Item::Struct(_))
+        ) {
+            abort!(
+                fqn,
+                format!(
+                    "Struct `{}` not found in the linked source code",
+                    ident_path.join("::")
+                )
+            );
+        }
+        if config.transparent_spec(&ident_path).is_some()
+            || config.oneof_enum_spec(&ident_path).is_some()
+        {
+            abort!(
+                fqn,
+                "Struct specified with `with_builder` can not be specified with `with_transparent` or `with_oneof_enum`"
+            );
+        }
+
+        let mut builders = vec![ident_path.last().unwrap().clone()];
+        builders.extend(
+            config
+                .spec_tree
+                .get_views(ident_path.iter())
+                .into_iter()
+                .filter_map(|view_spec| view_spec.view().map(ToString::to_string)),
+        );
+        for builder in builders.iter().map(|name| builder_name(name)) {
+            let builder_stack: Vec<String> = ident_path[..ident_path.len() - 1]
+                .iter()
+                .cloned()
+                .chain([builder.clone()])
+                .collect();
+            if super::find_item(&config.source.ast.items, &builder_stack).is_some() {
+                abort!(
+                    fqn,
+                    format!(
+                        "Builder name conflicts with the item of the linked source code: {}",
+                        builder_stack.join("::")
+                    )
+                );
+            }
+        }
+    }
+}
+
+fn builder_name(struct_name: &str) -> String {
+    format!("{struct_name}Builder")
+}
+
+/// Returns the mirror names of the required fields, which have no default
+/// value in the builder: the listed fields, the fields required by the
+/// validation rules and the required fields of the flattened messages.
+fn required_fields(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Vec<String> {
+    let listed_fields = struct_spec.map(StructSpec::fields_map).unwrap_or_default();
+    let mut required = Vec::new();
+    for field in &item.fields {
+        if struct_spec.and_then(|spec| spec.skip(field)).is_some() {
+            continue;
+        }
+        if let Some(flattened) =
+            super::item_flatten::flattened_field(config, field, ident_stack, struct_spec)
+        {
+            let Some(Item::Struct(message_item)) =
+                super::find_item(&config.source.ast.items, &flattened.message_stack)
+            else {
+                unreachable!("Expected flattened message to be a struct");
+            };
+            let message_spec = match config.spec_tree.get_leaf(flattened.message_stack.iter()) {
+                Some(SpecTreeLeaf::Struct(message_spec)) => Some(message_spec.as_ref()),
+                _ => None,
+            };
+            let message_required =
+                required_fields(config, message_item, &flattened.message_stack, message_spec);
+            required.extend(
+                flattened
+                    .fields
+                    .iter()
+                    .filter(|(mirror_field, _)| {
+                        message_required.contains(&mirror_field.ident.as_ref().unwrap().to_string())
+                    })
+                    .map(|(_, inlined_ident)| inlined_ident.to_string()),
+            );
+            continue;
+        }
+
+        let field_name = field.ident.as_ref().unwrap().to_string();
+        if listed_fields.contains_key(&field_name)
+            || (super::is_std_option_type(&field.ty)
+                && config.is_required_by_rules(ident_stack, &field_name))
+        {
+            required.push(
+                struct_spec
+                    .and_then(|spec| spec.field_rename(&field_name))
+                    .map(ToString::to_string)
+                    .unwrap_or(field_name),
+            );
+        }
+    }
+    required
+}
+
+const BUILDER_STRUCT: &str = r#"
+    pub struct {builder_name}{generics} {{
+        {fields}
+    }}
+"#;
+const BUILDER_ENTRY: &str = r#"
+    impl {struct_name} {{
+        pub fn builder() -> {builder_name}{unset_generics} {{
+            {builder_name} {{
+                {unset_fields}
+            }}
+        }}
+    }}
+"#;
+const BUILDER_REQUIRED_SETTER: &str = r#"
+    impl{impl_generics} {builder_name}{unset_generics} {{
+        pub fn {field_name}(self, {field_name}: {field_ty}) -> {builder_name}{set_generics} {{
+            {builder_name} {{
+                {moved_fields}
+            }}
+        }}
+    }}
+"#;
+const BUILDER_SETTER: &str = r#"
+    pub fn {field_name}(mut self, {field_name}: {field_ty}) -> Self {{
+        self.{field_name} = {field_value};
+        self
+    }}
+"#;
+const BUILDER_BUILD: &str = r#"
+    impl {builder_name}{set_generics} {{
+        pub fn build(self) -> {build_ty} {{
+            {build_expr}
+        }}
+    }}
+"#;
+
+/// Returns the typestate builder of the mirror struct, if the struct is
+/// specified with `with_builder`; the required fields are set once and must
+/// be set to build the mirror struct, the other fields have the default value.
+pub(crate) fn generate_builder(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Vec<Item> {
+    if config.builder_spec(ident_stack).is_none() {
+        return Vec::new();
+    }
+    let Item::Struct(mirror_struct) =
+        super::item_struct::mirror_struct(config, item, ident_stack, struct_spec)
+    else {
+        unreachable!("Expected struct to be mirrored as struct");
+    };
+    let Fields::Named(ref mirror_fields) = mirror_struct.fields else {
+        return Vec::new();
+    };
+    // the struct with private fields has the field accessors, which must not
+    // shadow the builder entry
+    for field in &mirror_fields.named {
+        let field_ident = field.ident.as_ref().unwrap();
+        if METHOD_NAMES.contains(&field_ident.to_string().as_str())
+            || (config.private_spec(ident_stack).is_some() && field_ident == "builder")
+        {
+            abort!(
+                config.builder_spec(ident_stack).unwrap(),
+                format!(
+                    "Field `{}` conflicts with the generated method of the builder",
+                    field_ident
+                )
+            );
+        }
+    }
+    let required = required_fields(config, item, ident_stack, struct_spec);
+    let struct_name = item.ident.to_string();
+    let builder_name = builder_name(&struct_name);
+
+    // every required field has its type parameter, which is `()` until the
+    // field is set and `(T,)` once it is
+    let fields: Vec<(String, String, Option<String>)> = mirror_fields
+        .named
+        .iter()
+        .map(|field| {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+            let field_ty = &field.ty;
+            let param = required
+                .iter()
+                .position(|name| *name == field_name)
+                .map(|position| format!("S{position}"));
+            (field_name, quote!(#field_ty).to_string(), param)
+        })
+        .collect();
+    let params: Vec<&str> = fields
+        .iter()
+        .filter_map(|(_, _, param)| param.as_deref())
+        .collect();
+    let generics = |args: Vec<String>| {
+        if args.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", args.join(", "))
+        }
+    };
+    let set_generics = generics(
+        fields
+            .iter()
+            .filter(|(_, _, param)| param.is_some())
+            .map(|(_, field_ty, _)| format!("({field_ty},)"))
+            .collect(),
+    );
+
+    let mut builder = vec![
+        strfmt!(
+            BUILDER_STRUCT,
+            builder_name => builder_name.clone(),
+            generics => generics(params.iter().map(ToString::to_string).collect()),
+            fields => fields
+                .iter()
+                .map(|(field_name, field_ty, param)| {
+                    format!("{field_name}: {},", param.as_ref().unwrap_or(field_ty))
+                })
+                .collect::<Vec<_>>()
+                .join("")
+        )
+        .unwrap(),
+        strfmt!(
+            BUILDER_ENTRY,
+            struct_name => struct_name.clone(),
+            builder_name => builder_name.clone(),
+            unset_generics => generics(params.iter().map(|_| "()".to_string()).collect()),
+            unset_fields => fields
+                .iter()
+                .map(|(field_name, _, param)| match param {
+                    Some(_) => format!("{field_name}: (),"),
+                    None => format!("{field_name}: Default::default(),"),
+                })
+                .collect::<Vec<_>>()
+                .join("")
+        )
+        .unwrap(),
+    ];
+
+    for (field_name, field_ty, param) in &fields {
+        let Some(param) = param else {
+            continue;
+        };
+        let other_params = |replacement: &str| {
+            params
+                .iter()
+                .map(|other| {
+                    if other == param {
+                        replacement.to_string()
+                    } else {
+                        other.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        builder.push(
+            strfmt!(
+                BUILDER_REQUIRED_SETTER,
+                builder_name => builder_name.clone(),
+                field_name => field_name.clone(),
+                field_ty => field_ty.clone(),
+                impl_generics => generics(
+                    params.iter().filter(|other| *other != param).map(ToString::to_string).collect()
+                ),
+                unset_generics => generics(other_params("()")),
+                set_generics => generics(other_params(&format!("({field_ty},)"))),
+                moved_fields => fields
+                    .iter()
+                    .map(|(other_name, _, _)| {
+                        if other_name == field_name {
+                            format!("{field_name}: ({field_name},),")
+                        } else {
+                            format!("{other_name}: self.{other_name},")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("")
+            )
+            .unwrap(),
+        );
+    }
+
+    // the optional field is set with its inner value
+    let mut setters = Vec::new();
+    for (field, (field_name, field_ty, param)) in mirror_fields.named.iter().zip(&fields) {
+        if param.is_some() {
+            continue;
+        }
+        let (field_ty, field_value) = if super::is_std_option_type(&field.ty) {
+            let inner_ty = super::maybe_unwrap_option_type(&field.ty);
+            (quote!(#inner_ty).to_string(), format!("Some({field_name})"))
+        } else {
+            (field_ty.clone(), field_name.clone())
+        };
+        setters.push(
+            strfmt!(
+                BUILDER_SETTER,
+                field_name => field_name.clone(),
+                field_ty => field_ty,
+                field_value => field_value
+            )
+            .unwrap(),
+        );
+    }
+    if !setters.is_empty() {
+        let param_generics = generics(params.iter().map(ToString::to_string).collect());
+        builder.push(format!(
+            "impl{param_generics} {builder_name}{param_generics} {{ {} }}",
+            setters.join("")
+        ));
+    }
+
+    // the struct with private fields is built with its constructor
+    let field_values: Vec<String> = fields
+        .iter()
+        .map(|(field_name, _, param)| match param {
+            Some(_) => format!("self.{field_name}.0"),
+            None => format!("self.{field_name}"),
+        })
+        .collect();
+    let (build_ty, build_expr) = if config.private_spec(ident_stack).is_some() {
+        let error_typepath =
+            config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+        (
+            format!("Result<{struct_name}, {}>", quote!(#error_typepath)),
+            format!("{struct_name}::new({})", field_values.join(", ")),
+        )
+    } else {
+        (
+            struct_name.clone(),
+            format!(
+                "{struct_name} {{ {} }}",
+                fields
+                    .iter()
+                    .zip(&field_values)
+                    .map(|((field_name, _, _), field_value)| format!(
+                        "{field_name}: {field_value},"
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("")
+            ),
+        )
+    };
+    builder.push(
+        strfmt!(
+            BUILDER_BUILD,
+            builder_name => builder_name.clone(),
+            set_generics => set_generics,
+            build_ty => build_ty,
+            build_expr => build_expr
+        )
+        .unwrap(),
+    );
+
+    let file: syn::File = syn::parse_str(&builder.join("")).expect("Expected builder definition");
+    file.items
+}
@@ -9,11 +9,20 @@ pub struct Enum;
 impl Traverse for Enum {
     type Item = ItemEnum;
 
-    fn traverse(_config: &Config, item: &Self::Item, _mod_stack: &mut Vec<String>) -> Vec<Item> {
+    fn traverse(config: &Config, item: &Self::Item, ident_stack: &mut Vec<String>) -> Vec<Item> {
         let mut mirror_enum = item.clone();
         super::drop_prost_derives(&mut mirror_enum.attrs);
         for variant in &mut mirror_enum.variants {
             super::drop_prost_attributes(&mut variant.attrs);
+            let variant_name = variant.ident.to_string();
+            for field in &mut variant.fields {
+                if let Some(mapping) =
+                    super::type_mapping(config, &field.ty, ident_stack, &variant_name)
+                {
+                    field.ty = mapping.ty;
+                }
+                super::resolve_type_paths(config, &mut field.ty, ident_stack, &variant_name);
+            }
         }
         vec![Item::Enum(mirror_enum)]
     }
@@ -34,6 +34,10 @@ const IMPL_BLOCK_TRY_FROM_VARIANT_NO_CONTENT: &str =
     "{item_enum_ty_path}::{variant_name} => Self::{variant_name},";
 const IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT: &str =
     "{item_enum_ty_path}::{variant_name}({fields}) => Self::{variant_name}({fields_into}),";
+const IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT_CONVERTED: &str =
+    ".try_into().map_err(|e| Self::Error::nested(\"{variant_name}\", e))?";
+const IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT_MAPPED: &str =
+    "{convert_expr}.map_err(|e| Self::Error::nested(\"{variant_name}\", e))?";
 const IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER: &str = "})}}";
 
 fn generate_try_from_original(
@@ -54,17 +58,44 @@ fn generate_try_from_original(
 
     for variant in &item.variants {
         if let Some(Field {
-            ty: Type::Path(ref ty_path),
+            ty: ref ty @ Type::Path(ref ty_path),
             ..
         }) = variant.fields.iter().next()
         {
-            if super::is_type_non_scalar(ty_path) {
+            if let Some(mapping) =
+                super::type_mapping(config, ty, ident_stack, &variant.ident.to_string())
+            {
                 try_from_impl_str += &strfmt!(
                     IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT,
                     variant_name => variant.ident.to_string(),
                     item_enum_ty_path => quote!(#orig_item_typepath).to_string(),
                     fields => variant_fields_as_string(&variant.fields, ""),
-                    fields_into => variant_fields_as_string(&variant.fields, ".try_into()?")
+                    fields_into => strfmt!(
+                        IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT_MAPPED,
+                        variant_name => variant.ident.to_string(),
+                        convert_expr => super::mapped_try_from_expr(
+                            super::FieldShape::Plain,
+                            &variant_fields_as_string(&variant.fields, ""),
+                            &mapping
+                        )
+                    )
+                    .unwrap()
+                )
+                .unwrap();
+            } else if super::is_type_non_scalar(ty_path) {
+                try_from_impl_str += &strfmt!(
+                    IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT,
+                    variant_name => variant.ident.to_string(),
+                    item_enum_ty_path => quote!(#orig_item_typepath).to_string(),
+                    fields => variant_fields_as_string(&variant.fields, ""),
+                    fields_into => variant_fields_as_string(
+                        &variant.fields,
+                        &strfmt!(
+                            IMPL_BLOCK_TRY_FROM_VARIANT_CONTENT_CONVERTED,
+                            variant_name => variant.ident.to_string()
+                        )
+                        .unwrap()
+                    )
                 )
                 .unwrap();
             } else {
@@ -85,9 +116,6 @@ fn generate_try_from_original(
             )
             .unwrap();
         }
-        if variant.fields.is_empty() {
-        } else {
-        }
     }
 
     try_from_impl_str += IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER;
@@ -123,11 +151,26 @@ fn generate_into_original(
 
     for variant in &item.variants {
         if let Some(Field {
-            ty: Type::Path(ref ty_path),
+            ty: ref ty @ Type::Path(ref ty_path),
             ..
         }) = variant.fields.iter().next()
         {
-            if super::is_type_non_scalar(ty_path) {
+            if let Some(mapping) =
+                super::type_mapping(config, ty, ident_stack, &variant.ident.to_string())
+            {
+                try_from_impl_str += &strfmt!(
+                    IMPL_BLOCK_INTO_VARIANT_CONTENT,
+                    variant_name => variant.ident.to_string(),
+                    item_enum_ty_path => quote!(#orig_item_typepath).to_string(),
+                    fields => variant_fields_as_string(&variant.fields, ""),
+                    fields_into => super::mapped_into_expr(
+                        super::FieldShape::Plain,
+                        &variant_fields_as_string(&variant.fields, ""),
+                        &mapping
+                    )
+                )
+                .unwrap();
+            } else if super::is_type_non_scalar(ty_path) {
                 try_from_impl_str += &strfmt!(
                     IMPL_BLOCK_INTO_VARIANT_CONTENT,
                     variant_name => variant.ident.to_string(),
@@ -0,0 +1,264 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::ext::IdentExt;
+use syn::Field;
+use syn::Fields;
+use syn::Ident;
+use syn::Item;
+use syn::ItemStruct;
+use syn::Path;
+use syn::Type;
+
+use crate::include::spec_tree::StructSpec;
+use crate::include::Config;
+use crate::include::FlattenSpec;
+use crate::traverse::Traverse;
+
+/// The required message field, which fields are inlined into the mirror
+/// struct, specified with `with_flatten`.
+pub(crate) struct Flattened<'a> {
+    pub spec: &'a FlattenSpec,
+    /// The message path relative to the linked source module
+    pub message_stack: Vec<String>,
+    /// The mirror struct path of the message
+    pub mirror_typepath: Path,
+    /// The mirror fields of the message, paired with the inlined field idents
+    pub fields: Vec<(Field, Ident)>,
+}
+
+/// Returns the flattened field of the struct, if the field is specified with
+/// `with_flatten`; aborts if the field is not a message field of the linked
+/// source code, or it is skipped, renamed or modified by the struct spec.
+pub(crate) fn flattened_field<'a>(
+    config: &'a Config,
+    field: &Field,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Option<Flattened<'a>> {
+    let field_name = field.ident.as_ref()?.to_string();
+    let spec = config.flatten_spec(ident_stack, &field_name)?;
+
+    if struct_spec.is_some_and(|struct_spec| {
+        struct_spec.skip(field).is_some()
+            || struct_spec.field_rename(&field_name).is_some()
+            || struct_spec.field_view(&field_name).is_some()
+            || struct_spec.modifier(&field_name).is_some()
+            || struct_spec.default(&field_name).is_some()
+    }) {
+        abort!(
+            spec.field,
+            "Flattened field can not be skipped, renamed or modified by `with_struct`"
+        );
+    }
+
+    let mut message_stack = match super::field_shape(&field.ty) {
+        (super::FieldShape::Option, Type::Path(ty_path)) => {
+            super::source_item_path(config, ty_path, ident_stack)
+        }
+        _ => None,
+    }
+    .filter(|message_stack| {
+        matches!(
+            super::find_item(&config.source.ast.items, message_stack),
+            Some(Item::Struct(_))
+        )
+    })
+    .unwrap_or_else(|| {
+        let ty = &field.ty;
+        abort!(
+            spec.field,
+            format!(
+                "Field has type `{}`, which is not an optional message of the linked source code",
+                quote!(#ty)
+            )
+        )
+    });
+    if config.private_spec(&message_stack).is_some() {
+        abort!(
+            spec.field,
+            "Message with private fields can not be flattened"
+        );
+    }
+    let Some(Item::Struct(message_item)) =
+        super::find_item(&config.source.ast.items, &message_stack)
+    else {
+        unreachable!("Expected flattened message to be a struct");
+    };
+
+    let mirror_struct = match super::item_struct::Struct::traverse(
+        config,
+        message_item,
+        &mut message_stack,
+    )
+    .pop()
+    {
+        Some(Item::Struct(mirror_struct)) => mirror_struct,
+        _ => unreachable!("Expected flattened message to be mirrored as struct"),
+    };
+    let fields = mirror_struct
+        .fields
+        .into_iter()
+        .map(|mirror_field| {
+            let mirror_ident = mirror_field.ident.clone().unwrap();
+            let name = format!("{}{}", spec.prefix, mirror_ident.unraw());
+            let ident = syn::parse_str::<Ident>(&name)
+                .unwrap_or_else(|_| Ident::new_raw(&name, spec.field.span()));
+            (mirror_field, ident)
+        })
+        .collect();
+
+    Some(Flattened {
+        spec,
+        mirror_typepath: config.this_item_typepath(message_stack.iter().cloned()),
+        message_stack,
+        fields,
+    })
+}
+
+/// Replaces the flattened fields of the mirror struct with the inlined ones;
+/// aborts if the inlined field conflicts with another field of the mirror
+/// struct.
+pub(crate) fn flatten_fields(
+    config: &Config,
+    item: &ItemStruct,
+    mirror_struct: &mut ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) {
+    validate_flattened_fields(config, item, ident_stack);
+
+    let Fields::Named(ref mut mirror_fields) = mirror_struct.fields else {
+        return;
+    };
+    let mut inlined_fields: Vec<(Field, &Ident)> = Vec::new();
+    for field in &item.fields {
+        let Some(flattened) = flattened_field(config, field, ident_stack, struct_spec) else {
+            continue;
+        };
+        let position = mirror_fields
+            .named
+            .iter()
+            .position(|mirror_field| mirror_field.ident == field.ident)
+            .expect("Expected flattened field to be mirrored");
+        let inlined: Vec<Field> = flattened
+            .fields
+            .into_iter()
+            .map(|(mut mirror_field, ident)| {
+                mirror_field.ident = Some(ident);
+                mirror_field
+            })
+            .collect();
+        let mut named: Vec<Field> = mirror_fields.named.clone().into_iter().collect();
+        named.splice(position..=position, inlined.iter().cloned());
+        mirror_fields.named = named.into_iter().collect();
+        inlined_fields.extend(
+            inlined
+                .into_iter()
+                .map(|inlined_field| (inlined_field, &flattened.spec.field)),
+        );
+    }
+
+    for (inlined_field, flattened_field) in &inlined_fields {
+        let inlined_ident = inlined_field.ident.as_ref();
+        let occurrences = mirror_fields
+            .named
+            .iter()
+            .filter(|mirror_field| mirror_field.ident.as_ref() == inlined_ident)
+            .count();
+        if occurrences > 1 {
+            abort!(
+                flattened_field,
+                format!(
+                    "Flattened field `{}` conflicts with another field of the mirror struct",
+                    inlined_ident.unwrap()
+                )
+            );
+        }
+    }
+}
+
+/// Aborts if the flattened field is missing from the struct definition.
+fn validate_flattened_fields(config: &Config, item: &ItemStruct, ident_stack: &[String]) {
+    for flatten in config.flatten_specs(ident_stack) {
+        if !item
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(&flatten.field))
+        {
+            abort!(
+                flatten.field,
+                format!(
+                    "Flattened field missing from struct definition: {}",
+                    flatten.field
+                )
+            );
+        }
+    }
+}
+
+const TRY_FROM_PRELUDE: &str = r#"
+    let {var_name}: {mirror_typepath} = value.{field_name}
+        .ok_or_else(|| Self::Error::required("{field_name}"))?
+        .try_into()
+        .map_err(|e| Self::Error::nested("{field_name}", e))?;
+"#;
+const TRY_FROM_FIELD: &str = "{inlined_name}: {var_name}.{mirror_name},";
+const INTO_HEADER: &str = "{field_name}: Some({mirror_typepath} {{";
+const INTO_FIELD: &str = "{mirror_name}: self.{inlined_name},";
+const INTO_FOOTER: &str = "}.into()),";
+
+impl Flattened<'_> {
+    /// Returns the statement converting the message into its mirror struct
+    /// and the mirror struct fields initializers, destructuring it.
+    pub fn destructuring_code(&self) -> (String, Vec<String>) {
+        let field_name = self.spec.field.to_string();
+        let var_name = format!("flattened_{}", self.spec.field.unraw());
+        let mirror_typepath = &self.mirror_typepath;
+        let prelude = strfmt!(
+            TRY_FROM_PRELUDE,
+            var_name => var_name.clone(),
+            field_name => field_name,
+            mirror_typepath => quote!(#mirror_typepath).to_string()
+        )
+        .unwrap();
+        let fields = self
+            .fields
+            .iter()
+            .map(|(mirror_field, inlined_ident)| {
+                strfmt!(
+                    TRY_FROM_FIELD,
+                    inlined_name => inlined_ident.to_string(),
+                    var_name => var_name.clone(),
+                    mirror_name => mirror_field.ident.as_ref().unwrap().to_string()
+                )
+                .unwrap()
+            })
+            .collect();
+        (prelude, fields)
+    }
+
+    /// Returns the original struct field initializer, rebuilding the message
+    /// from the inlined fields.
+    pub fn rebuilding_code(&self) -> String {
+        let mirror_typepath = &self.mirror_typepath;
+        let mut code = vec![strfmt!(
+            INTO_HEADER,
+            field_name => self.spec.field.to_string(),
+            mirror_typepath => quote!(#mirror_typepath).to_string()
+        )
+        .unwrap()];
+        for (mirror_field, inlined_ident) in &self.fields {
+            code.push(
+                strfmt!(
+                    INTO_FIELD,
+                    mirror_name => mirror_field.ident.as_ref().unwrap().to_string(),
+                    inlined_name => inlined_ident.to_string()
+                )
+                .unwrap(),
+            );
+        }
+        code.push(INTO_FOOTER.to_string());
+        code.join("")
+    }
+}
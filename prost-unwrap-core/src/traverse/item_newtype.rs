@@ -0,0 +1,196 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Field;
+use syn::Fields;
+use syn::Item;
+use syn::Type;
+
+use super::TypeMapping;
+use crate::include::Config;
+use crate::include::NewtypeSpec;
+
+/// The scalar types, that may be wrapped into the newtype.
+const NEWTYPE_INNER_TYPES: [&str; 8] = ["String", "i32", "i64", "u32", "u64", "f32", "f64", "bool"];
+
+/// Returns the mapping of the field wrapped into the newtype.
+pub(crate) fn type_mapping(
+    config: &Config,
+    ident_stack: &[String],
+    field_name: &str,
+) -> Option<TypeMapping> {
+    const TRY_FROM_FN: &str = "|value| Ok::<_, {error_typepath}>({newtype_typepath}(value))";
+    const INTO_FN: &str = "|value: {newtype_typepath}| value.0";
+
+    let newtype = config.newtype_spec(ident_stack, field_name)?;
+    let newtype_typepath = config.this_item_typepath([newtype.ident.to_string()]);
+    let error_typepath = config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+
+    let try_from_fn = strfmt!(
+        TRY_FROM_FN,
+        newtype_typepath => quote!(#newtype_typepath).to_string(),
+        error_typepath => quote!(#error_typepath).to_string()
+    )
+    .unwrap();
+    let into_fn = strfmt!(
+        INTO_FN,
+        newtype_typepath => quote!(#newtype_typepath).to_string()
+    )
+    .unwrap();
+
+    Some(TypeMapping {
+        ty: syn::parse_quote!(#newtype_typepath),
+        try_from_fn: syn::parse_str(&try_from_fn).expect("Expected newtype conversion closure"),
+        into_fn: syn::parse_str(&into_fn).expect("Expected newtype conversion closure"),
+    })
+}
+
+/// Returns the newtypes definitions; each newtype is defined once, wrapping
+/// the element type of the fields it is used for.
+pub(crate) fn items(config: &Config) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut defined: Vec<(&NewtypeSpec, Type)> = Vec::new();
+
+    for newtype in &config.newtypes {
+        let inner_ty = newtype_inner_type(config, newtype);
+        match defined
+            .iter()
+            .find(|(defined_newtype, _)| defined_newtype.ident == newtype.ident)
+        {
+            Some((_, defined_ty)) if *defined_ty == inner_ty => (),
+            Some((defined_newtype, defined_ty)) => abort!(
+                newtype.field,
+                format!(
+                    "Newtype `{}` wraps `{}` type of `{}` field, but field `{}` has `{}` type",
+                    newtype.ident,
+                    quote!(#defined_ty),
+                    defined_newtype.field_path(),
+                    newtype.field_path(),
+                    quote!(#inner_ty)
+                )
+            ),
+            None => {
+                items.extend(item_newtype(&newtype.ident.to_string(), &inner_ty));
+                defined.push((newtype, inner_ty));
+            }
+        }
+    }
+
+    items
+}
+
+/// Returns the element type of the newtype field in the linked source code.
+fn newtype_inner_type(config: &Config, newtype: &NewtypeSpec) -> Type {
+    let Some(field) = find_field(&config.source.ast.items, newtype) else {
+        abort!(
+            newtype.field,
+            format!(
+                "Field `{}` not found in the linked source code",
+                newtype.field_path()
+            )
+        );
+    };
+
+    let (_, inner_ty) = super::field_shape(&field.ty);
+    let is_scalar = match inner_ty {
+        Type::Path(ty_path) => ty_path.path.segments.last().is_some_and(|segment| {
+            NEWTYPE_INNER_TYPES.contains(&segment.ident.to_string().as_str())
+        }),
+        _ => false,
+    };
+    if !is_scalar {
+        let ty = &field.ty;
+        abort!(
+            newtype.field,
+            format!(
+                "Field has type `{}`, which can not be wrapped into newtype; the supported types are: {}",
+                quote!(#ty),
+                NEWTYPE_INNER_TYPES.join(", ")
+            )
+        );
+    }
+
+    inner_ty.clone()
+}
+
+fn find_field<'a>(items: &'a [Item], newtype: &NewtypeSpec) -> Option<&'a Field> {
+    let ident_path: Vec<String> = newtype
+        .fqn
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+
+    match super::find_item(items, &ident_path)? {
+        Item::Struct(item_struct) => match item_struct.fields {
+            Fields::Named(ref fields) => fields
+                .named
+                .iter()
+                .find(|field| field.ident.as_ref() == Some(&newtype.field)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn item_newtype(newtype_name: &str, inner_ty: &Type) -> Vec<Item> {
+    const DEF_BLOCK: &str = r#"
+        #[derive({derives})]
+        #[repr(transparent)]
+        pub struct {newtype_name}(pub {inner_ty});
+
+        impl std::fmt::Display for {newtype_name} {{
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+                std::fmt::Display::fmt(&self.0, f)
+            }}
+        }}
+
+        impl std::str::FromStr for {newtype_name} {{
+            type Err = <{inner_ty} as std::str::FromStr>::Err;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {{
+                s.parse().map(Self)
+            }}
+        }}
+
+        impl AsRef<{inner_ty}> for {newtype_name} {{
+            fn as_ref(&self) -> &{inner_ty} {{
+                &self.0
+            }}
+        }}
+
+        impl From<{inner_ty}> for {newtype_name} {{
+            fn from(value: {inner_ty}) -> Self {{
+                Self(value)
+            }}
+        }}
+
+        impl From<{newtype_name}> for {inner_ty} {{
+            fn from(value: {newtype_name}) -> Self {{
+                value.0
+            }}
+        }}
+    "#;
+
+    let inner_ident = match inner_ty {
+        Type::Path(ty_path) => ty_path.path.segments.last().unwrap().ident.to_string(),
+        _ => unreachable!("Expected newtype inner type to be scalar"),
+    };
+    let mut derives = vec!["Clone", "Debug", "Default", "PartialEq", "PartialOrd"];
+    if inner_ident != "String" {
+        derives.push("Copy");
+    }
+    if inner_ident != "f32" && inner_ident != "f64" {
+        derives.extend(["Eq", "Ord", "Hash"]);
+    }
+
+    let def_block = strfmt!(
+        DEF_BLOCK,
+        derives => derives.join(", "),
+        newtype_name => newtype_name.to_string(),
+        inner_ty => quote!(#inner_ty).to_string()
+    )
+    .unwrap();
+    let file: syn::File = syn::parse_str(&def_block).expect("Expected newtype definition");
+    file.items
+}
@@ -0,0 +1,197 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Fields;
+use syn::Ident;
+use syn::Item;
+use syn::ItemEnum;
+use syn::ItemStruct;
+use syn::Path;
+
+use crate::include::Config;
+use crate::traverse::Traverse;
+
+/// Mirrors the struct with a single oneof field as the enum, specified with
+/// `with_oneof_enum`; the enum has the variants of the mirrored oneof enum,
+/// which is converted from and into.
+pub struct OneofEnum;
+
+impl Traverse for OneofEnum {
+    type Item = ItemStruct;
+
+    fn traverse(config: &Config, item: &Self::Item, ident_stack: &mut Vec<String>) -> Vec<Item> {
+        let fqn = config
+            .oneof_enum_spec(ident_stack)
+            .expect("Expected struct to be specified with `with_oneof_enum`");
+        if config.spec_tree.get_leaf(ident_stack.iter()).is_some() {
+            abort!(
+                fqn,
+                "Struct specified with `with_oneof_enum` can not be specified with `with_struct`"
+            );
+        }
+
+        let (field_ident, mut enum_stack, item_enum) = oneof_field(config, item, ident_stack, fqn);
+
+        // the enum is mirrored the same way the oneof enum is, but named after
+        // the struct
+        let mut mirror_enum =
+            match super::item_enum::Enum::traverse(config, item_enum, &mut enum_stack).pop() {
+                Some(Item::Enum(mirror_enum)) => mirror_enum,
+                _ => unreachable!("Expected oneof enum to be mirrored as enum"),
+            };
+        mirror_enum.ident = item.ident.clone();
+
+        let mut vec = vec![Item::Enum(mirror_enum)];
+        vec.extend(generate_try_from_original(
+            config,
+            item,
+            ident_stack,
+            field_ident,
+            &enum_stack,
+            item_enum,
+        ));
+        vec.extend(generate_into_original(
+            config,
+            item,
+            ident_stack,
+            field_ident,
+            &enum_stack,
+            item_enum,
+        ));
+        vec
+    }
+}
+
+/// Returns the single oneof field of the struct, the oneof enum path and the
+/// oneof enum item; aborts if the struct has other fields.
+fn oneof_field<'a>(
+    config: &'a Config,
+    item: &'a ItemStruct,
+    ident_stack: &[String],
+    fqn: &Path,
+) -> (&'a Ident, Vec<String>, &'a ItemEnum) {
+    if let Fields::Named(ref fields) = item.fields {
+        if fields.named.len() == 1 {
+            let field = fields.named.first().unwrap();
+            if let Some((enum_stack, item_enum)) = super::oneof_enum(config, &field.ty, ident_stack)
+            {
+                return (field.ident.as_ref().unwrap(), enum_stack, item_enum);
+            }
+        }
+    }
+    abort!(
+        fqn,
+        "Expected struct to have a single oneof field to be mirrored as enum"
+    );
+}
+
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_HEADER: &str = r#"
+    impl std::convert::TryFrom<{orig_item_typepath}> for {enum_name} {{
+        type Error = {error_typepath};
+
+        fn try_from(value: {orig_item_typepath}) -> Result<Self, Self::Error> {{
+            let oneof = value.{field_name}.ok_or_else(|| Self::Error::required("{field_name}"))?;
+            Ok(match {mirror_oneof_typepath}::try_from(oneof)
+                .map_err(|e| Self::Error::nested("{field_name}", e))?
+            {{
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_VARIANT: &str =
+    "{mirror_oneof_typepath}::{variant_name}(value) => Self::{variant_name}(value),";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER: &str = "})}}";
+
+fn generate_try_from_original(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    field_ident: &Ident,
+    enum_stack: &[String],
+    item_enum: &ItemEnum,
+) -> Vec<Item> {
+    let orig_item_typepath = config.orig_item_typepath(ident_stack.iter().cloned());
+    let mirror_oneof_typepath = config.this_item_typepath(enum_stack.iter().cloned());
+    let error_typepath = config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+
+    let mut try_from_impl = vec![strfmt!(
+        IMPL_BLOCK_TRY_FROM_ORIGINAL_HEADER,
+        enum_name => item.ident.to_string(),
+        field_name => field_ident.to_string(),
+        orig_item_typepath => quote!(#orig_item_typepath).to_string(),
+        mirror_oneof_typepath => quote!(#mirror_oneof_typepath).to_string(),
+        error_typepath => quote!(#error_typepath).to_string()
+    )
+    .unwrap()];
+
+    for variant in &item_enum.variants {
+        try_from_impl.push(
+            strfmt!(
+                IMPL_BLOCK_TRY_FROM_ORIGINAL_VARIANT,
+                variant_name => variant.ident.to_string(),
+                mirror_oneof_typepath => quote!(#mirror_oneof_typepath).to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    try_from_impl.push(IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER.to_string());
+    let try_from_impl_block: Item = syn::parse_str(try_from_impl.join("").as_str()).unwrap();
+
+    vec![try_from_impl_block]
+}
+
+const IMPL_BLOCK_INTO_ORIGINAL_HEADER: &str = r#"
+    impl std::convert::Into<{orig_item_typepath}> for {enum_name} {{
+        fn into(self) -> {orig_item_typepath} {{
+            let oneof = match self {{
+"#;
+const IMPL_BLOCK_INTO_ORIGINAL_VARIANT: &str =
+    "Self::{variant_name}(value) => {mirror_oneof_typepath}::{variant_name}(value),";
+const IMPL_BLOCK_INTO_ORIGINAL_FOOTER: &str = r#"
+            }};
+            {orig_item_typepath} {{
+                {field_name}: Some(oneof.into()),
+            }}
+        }}
+    }}
+"#;
+
+fn generate_into_original(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    field_ident: &Ident,
+    enum_stack: &[String],
+    item_enum: &ItemEnum,
+) -> Vec<Item> {
+    let orig_item_typepath = config.orig_item_typepath(ident_stack.iter().cloned());
+    let mirror_oneof_typepath = config.this_item_typepath(enum_stack.iter().cloned());
+
+    let mut into_impl = vec![strfmt!(
+        IMPL_BLOCK_INTO_ORIGINAL_HEADER,
+        enum_name => item.ident.to_string(),
+        orig_item_typepath => quote!(#orig_item_typepath).to_string()
+    )
+    .unwrap()];
+
+    for variant in &item_enum.variants {
+        into_impl.push(
+            strfmt!(
+                IMPL_BLOCK_INTO_ORIGINAL_VARIANT,
+                variant_name => variant.ident.to_string(),
+                mirror_oneof_typepath => quote!(#mirror_oneof_typepath).to_string()
+            )
+            .unwrap(),
+        );
+    }
+
+    into_impl.push(
+        strfmt!(
+            IMPL_BLOCK_INTO_ORIGINAL_FOOTER,
+            field_name => field_ident.to_string(),
+            orig_item_typepath => quote!(#orig_item_typepath).to_string()
+        )
+        .unwrap(),
+    );
+    let into_impl_block: Item = syn::parse_str(into_impl.join("").as_str()).unwrap();
+
+    vec![into_impl_block]
+}
@@ -0,0 +1,263 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Fields;
+use syn::Item;
+use syn::ItemStruct;
+use syn::Visibility;
+
+use crate::include::spec_tree::FieldModifier;
+use crate::include::spec_tree::StructSpec;
+use crate::include::Config;
+
+/// The methods generated for the struct with private fields, which must not
+/// be shadowed by the field accessors.
+const METHOD_NAMES: [&str; 2] = ["new", "into_parts"];
+
+/// Makes the mirror struct fields private, if the struct is specified with
+/// `with_private_fields`.
+pub(crate) fn hide_fields(config: &Config, mirror_struct: &mut ItemStruct, ident_stack: &[String]) {
+    if config.private_spec(ident_stack).is_none() {
+        return;
+    }
+    for field in &mut mirror_struct.fields {
+        field.vis = Visibility::Inherited;
+    }
+}
+
+/// Aborts if any of the `with_private_fields` structs is not a struct of the
+/// linked source code.
+pub(crate) fn validate_specs(config: &Config) {
+    for fqn in &config.privates {
+        let ident_path: Vec<String> = fqn
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if !matches!(
+            super::find_item(&config.source.ast.items, &ident_path),
+            Some(Item::Struct(_))
+        ) {
+            abort!(
+                fqn,
+                format!(
+                    "Struct `{}` not found in the linked source code",
+                    ident_path.join("::")
+                )
+            );
+        }
+        if config.transparent_spec(&ident_path).is_some()
+            || config.oneof_enum_spec(&ident_path).is_some()
+        {
+            abort!(
+                fqn,
+                "Struct specified with `with_private_fields` can not be specified with `with_transparent` or `with_oneof_enum`"
+            );
+        }
+    }
+}
+
+const IMPL_BLOCK_HEADER: &str = r#"
+    impl {struct_name} {{
+        #[allow(clippy::too_many_arguments)]
+        pub fn new({params}) -> Result<Self, {error_typepath}> {{
+"#;
+const IMPL_BLOCK_PARAM: &str = "{field_name}: {field_ty},";
+const IMPL_BLOCK_CHECK_NON_EMPTY: &str = r#"
+    if {field_name}.is_empty() {{
+        return Err({error_typepath}::new("field is empty").at("{field_name}"));
+    }}
+"#;
+const IMPL_BLOCK_CHECK_NON_ZERO: &str = r#"
+    if {field_name} == <{field_ty} as Default>::default() {{
+        return Err({error_typepath}::new("field is zero").at("{field_name}"));
+    }}
+"#;
+const IMPL_BLOCK_BODY: &str = "let mirror = Self {{ {field_names} }};";
+const IMPL_BLOCK_VALIDATE: &str = r#"
+    {validator}(&{value}).map_err({error_typepath}::custom)?;
+"#;
+const IMPL_BLOCK_ACCESSOR: &str = r#"
+    pub fn {field_name}(&self) -> &{field_ty} {{
+        &self.{field_name}
+    }}
+"#;
+const IMPL_BLOCK_INTO_PARTS: &str = r#"
+    pub fn into_parts(self) -> ({field_tys}) {{
+        ({field_values})
+    }}
+"#;
+
+/// Returns the constructor, the field accessors and `into_parts` of the
+/// mirror struct with private fields; the constructor checks the field
+/// modifiers and validation rules, and calls the validators, as `TryFrom`
+/// does.
+pub(crate) fn generate_impl(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Vec<Item> {
+    if config.private_spec(ident_stack).is_none() {
+        return Vec::new();
+    }
+    let Item::Struct(mirror_struct) =
+        super::item_struct::mirror_struct(config, item, ident_stack, struct_spec)
+    else {
+        unreachable!("Expected struct to be mirrored as struct");
+    };
+    let Fields::Named(ref mirror_fields) = mirror_struct.fields else {
+        return Vec::new();
+    };
+    let error_typepath = config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+    let error_typepath = quote!(#error_typepath).to_string();
+
+    let fields: Vec<(String, String)> = mirror_fields
+        .named
+        .iter()
+        .map(|field| {
+            let field_ident = field.ident.as_ref().unwrap();
+            if METHOD_NAMES.contains(&field_ident.to_string().as_str()) {
+                abort!(
+                    config.private_spec(ident_stack).unwrap(),
+                    format!(
+                        "Field `{}` conflicts with the generated method of the mirror struct",
+                        field_ident
+                    )
+                );
+            }
+            let field_ty = &field.ty;
+            (field_ident.to_string(), quote!(#field_ty).to_string())
+        })
+        .collect();
+
+    let params: Vec<String> = fields
+        .iter()
+        .map(|(field_name, field_ty)| {
+            strfmt!(
+                IMPL_BLOCK_PARAM,
+                field_name => field_name.clone(),
+                field_ty => field_ty.clone()
+            )
+            .unwrap()
+        })
+        .collect();
+    let mut impl_block = vec![strfmt!(
+        IMPL_BLOCK_HEADER,
+        struct_name => item.ident.to_string(),
+        params => params.join(""),
+        error_typepath => error_typepath.clone()
+    )
+    .unwrap()];
+
+    // the field modifiers and the validation rules are checked on the mirror
+    // field values, the mirror field may be renamed
+    for field in &item.fields {
+        let field_name = field.ident.as_ref().unwrap().to_string();
+        let mirror_field_name = struct_spec
+            .and_then(|spec| spec.field_rename(&field_name))
+            .map(ToString::to_string)
+            .unwrap_or_else(|| field_name.clone());
+        // the skipped field is not mirrored
+        let Some(mirror_field) = mirror_fields
+            .named
+            .iter()
+            .find(|mirror_field| *mirror_field.ident.as_ref().unwrap() == mirror_field_name)
+        else {
+            continue;
+        };
+        let field_ty = &mirror_field.ty;
+        let check_template = match struct_spec.and_then(|spec| spec.modifier(&field_name)) {
+            // `NonEmpty<Vec<T>>` is checked as well, as `TryFrom` does
+            Some(FieldModifier::NonEmpty | FieldModifier::NonEmptyVec) => {
+                Some(IMPL_BLOCK_CHECK_NON_EMPTY)
+            }
+            Some(FieldModifier::NonZero) => Some(IMPL_BLOCK_CHECK_NON_ZERO),
+            Some(FieldModifier::Variant(_)) | None => None,
+        };
+        if let Some(check_template) = check_template {
+            impl_block.push(
+                strfmt!(
+                    check_template,
+                    field_name => mirror_field_name.clone(),
+                    field_ty => quote!(#field_ty).to_string(),
+                    error_typepath => error_typepath.clone()
+                )
+                .unwrap(),
+            );
+        }
+        impl_block.extend(super::field_rules::argument_checks(
+            config,
+            ident_stack,
+            &field_name,
+            &field.ty,
+            &mirror_field_name,
+            field_ty,
+            &error_typepath,
+        ));
+    }
+
+    let field_names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+    impl_block.push(
+        strfmt!(
+            IMPL_BLOCK_BODY,
+            field_names => field_names.join(", ")
+        )
+        .unwrap(),
+    );
+    // the original struct validators are called on the mirror converted into
+    // the original struct, before the mirror struct validators
+    let orig_item_typepath = config.orig_item_typepath(ident_stack.iter().cloned());
+    let mut validator_specs: Vec<_> = config.validator_specs(ident_stack).collect();
+    validator_specs.sort_by_key(|validator_spec| !validator_spec.original);
+    for validator_spec in validator_specs {
+        let validator = &validator_spec.validator;
+        let value = if validator_spec.original {
+            format!(
+                "Into::<{}>::into(mirror.clone())",
+                quote!(#orig_item_typepath)
+            )
+        } else {
+            "mirror".to_string()
+        };
+        impl_block.push(
+            strfmt!(
+                IMPL_BLOCK_VALIDATE,
+                validator => quote!(#validator).to_string(),
+                value => value,
+                error_typepath => error_typepath.clone()
+            )
+            .unwrap(),
+        );
+    }
+    impl_block.push("Ok(mirror) }".to_string());
+
+    for (field_name, field_ty) in &fields {
+        impl_block.push(
+            strfmt!(
+                IMPL_BLOCK_ACCESSOR,
+                field_name => field_name.clone(),
+                field_ty => field_ty.clone()
+            )
+            .unwrap(),
+        );
+    }
+    // the trailing commas keep the parts of a single field struct a tuple
+    let field_tys: Vec<String> = fields.iter().map(|(_, ty)| format!("{ty},")).collect();
+    let field_values: Vec<String> = field_names
+        .iter()
+        .map(|field_name| format!("self.{field_name},"))
+        .collect();
+    impl_block.push(
+        strfmt!(
+            IMPL_BLOCK_INTO_PARTS,
+            field_tys => field_tys.join(" "),
+            field_values => field_values.join(" ")
+        )
+        .unwrap(),
+    );
+    impl_block.push("}".to_string());
+
+    let impl_block: Item = syn::parse_str(impl_block.join("").as_str()).unwrap();
+    vec![impl_block]
+}
@@ -1,10 +1,14 @@
 use proc_macro_error::abort;
 use quote::quote;
 use syn::Fields;
+use syn::Ident;
 use syn::Item;
 use syn::ItemStruct;
+use syn::Type;
 
+use crate::include::spec_tree::FieldModifier;
 use crate::include::spec_tree::SpecTreeLeaf;
+use crate::include::spec_tree::StructSpec;
 use crate::include::Config;
 use crate::traverse::Traverse;
 
@@ -19,81 +23,370 @@ impl Traverse for Struct {
             .map(ToString::to_string)
             .collect::<Vec<_>>();
 
-        let mirror_struct = match config.spec_tree.get_leaf(&mirror_struct_path) {
-            None => {
-                let mut mirror_struct = item.clone();
-                super::drop_prost_derives(&mut mirror_struct.attrs);
-                for field in &mut mirror_struct.fields {
-                    super::drop_prost_attributes(&mut field.attrs);
-                }
-                Item::Struct(mirror_struct)
-            }
+        validate_optional_fields(config, item, ident_stack);
+
+        let struct_spec = match config.spec_tree.get_leaf(&mirror_struct_path) {
+            None => None,
             Some(enum_leaf @ SpecTreeLeaf::Enum { .. }) => abort!(
                 enum_leaf.fqn_ref(),
                 "Expected specified item to be enum, but struct found"
             ),
-            Some(struct_leaf @ SpecTreeLeaf::Struct(struct_spec)) => {
-                let mut mirror_struct = item.clone();
-
-                super::drop_prost_derives(&mut mirror_struct.attrs);
-
-                let mut required_fields = struct_spec.fields_map();
-                match mirror_struct.fields {
-                    Fields::Named(ref mut fields) => {
-                        for field in &mut fields.named {
-                            super::drop_prost_attributes(&mut field.attrs);
-
-                            let field_name = field
-                                .ident
-                                .as_ref()
-                                .expect("Expected field ident to be Some")
-                                .to_string();
-                            let is_required_field = required_fields.contains_key(&field_name);
-                            let is_std_option_type = super::is_std_option_type(&field.ty);
-                            let is_std_vec_type = super::is_std_vec_type(&field.ty);
-
-                            match (is_required_field, is_std_option_type, is_std_vec_type) {
-                                (true, true, _) => {
-                                    let ty = super::maybe_unwrap_option_type(&field.ty);
-                                    field.ty = ty.clone();
-                                }
-                                (true, false, _) => {
-                                    let ty = &field.ty;
-                                    abort!(
-                                        required_fields.get(&field_name).unwrap(),
-                                        format!(
-                                            "Field has type `{}`, which is not an Option<T> type",
-                                            quote!(#ty)
-                                        )
-                                    );
-                                }
-                                (_, _, _) => (),
-                            }
+            Some(SpecTreeLeaf::Struct(struct_spec)) => Some(struct_spec.as_ref()),
+        };
+
+        vec![mirror_struct(config, item, ident_stack, struct_spec)]
+    }
+}
+
+/// Returns the mirror struct of the original struct, unwrapping the fields
+/// listed in the struct spec; the mirror struct is named after the `item`
+/// ident, which differs from the original one for the named view.
+pub(crate) fn mirror_struct(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Item {
+    let Item::Struct(mut mirror_struct) =
+        mirror_struct_fields(config, item, ident_stack, struct_spec)
+    else {
+        unreachable!("Expected struct to be mirrored as struct");
+    };
+    // the flattened fields are inlined once the other fields are resolved
+    super::item_flatten::flatten_fields(config, item, &mut mirror_struct, ident_stack, struct_spec);
+    super::item_private::hide_fields(config, &mut mirror_struct, ident_stack);
+    Item::Struct(mirror_struct)
+}
+
+fn mirror_struct_fields(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Item {
+    match struct_spec {
+        None => {
+            let mut mirror_struct = item.clone();
+            super::drop_prost_derives(&mut mirror_struct.attrs);
+            for field in &mut mirror_struct.fields {
+                super::drop_prost_attributes(&mut field.attrs);
+                let field_name = field
+                    .ident
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let is_optional = is_optional_field(config, &field.ty, ident_stack, &field_name);
+                map_field_type(config, &mut field.ty, ident_stack, &field_name);
+                super::resolve_type_paths(config, &mut field.ty, ident_stack, &field_name);
+                if is_optional {
+                    wrap_option_type(&mut field.ty);
+                }
+                if super::is_std_option_type(&field.ty)
+                    && config.is_required_by_rules(ident_stack, &field_name)
+                {
+                    field.ty = super::maybe_unwrap_option_type(&field.ty).clone();
+                }
+            }
+            Item::Struct(mirror_struct)
+        }
+        Some(struct_spec) => {
+            let mut mirror_struct = item.clone();
+
+            super::drop_prost_derives(&mut mirror_struct.attrs);
 
+            let mut required_fields = struct_spec.fields_map();
+            match mirror_struct.fields {
+                Fields::Named(ref mut fields) => {
+                    validate_skipped_fields(item, struct_spec);
+                    // the skipped fields are excluded from the mirror struct
+                    fields.named = fields
+                        .named
+                        .clone()
+                        .into_iter()
+                        .filter(|field| struct_spec.skip(field).is_none())
+                        .collect();
+                    for field in &mut fields.named {
+                        super::drop_prost_attributes(&mut field.attrs);
+
+                        let field_name = field
+                            .ident
+                            .as_ref()
+                            .expect("Expected field ident to be Some")
+                            .to_string();
+                        let orig_ty = field.ty.clone();
+                        let modifier = struct_spec.modifier(&field_name);
+                        if let Some(modifier) = &modifier {
+                            validate_field_modifier(
+                                required_fields.get(&field_name).unwrap(),
+                                &field.ty,
+                                modifier,
+                            );
+                        }
+                        // the oneof field narrowed to a single variant has
+                        // the variant inner type
+                        if let Some(FieldModifier::Variant(variant)) = &modifier {
+                            field.ty =
+                                super::oneof_variant(config, &field.ty, ident_stack, variant).ty;
                             required_fields.remove(&field_name);
+                            continue;
                         }
-
-                        if let Some((field_name, field_ident)) = required_fields.into_iter().next()
-                        {
+                        let is_optional =
+                            is_optional_field(config, &field.ty, ident_stack, &field_name);
+                        if is_optional && required_fields.contains_key(&field_name) {
                             abort!(
-                                field_ident,
+                                required_fields.get(&field_name).unwrap(),
                                 format!(
-                                    "Required field missing from struct definition: {}",
+                                    "Field `{}` is specified with `with_optional` and can not be listed",
                                     field_name
                                 )
-                            )
+                            );
                         }
+                        map_field_type(config, &mut field.ty, ident_stack, &field_name);
+                        super::resolve_type_paths(config, &mut field.ty, ident_stack, &field_name);
+                        if let Some(field_view) = struct_spec.field_view(&field_name) {
+                            super::item_view::apply_field_view(
+                                config,
+                                &mut field.ty,
+                                &orig_ty,
+                                ident_stack,
+                                &field_name,
+                                field_view,
+                            );
+                        }
+                        if is_optional {
+                            wrap_option_type(&mut field.ty);
+                        }
+                        if modifier == Some(FieldModifier::NonEmptyVec) {
+                            let ty = &field.ty;
+                            field.ty = syn::parse_quote!(::prost_unwrap::NonEmpty<#ty>);
+                        }
+                        let is_std_option_type = super::is_std_option_type(&field.ty);
+                        // the listed field with modifier keeps its type, the
+                        // `Option<T>` field marked as `required` by the
+                        // validation rules is unwrapped as well
+                        let is_required_field = (required_fields.contains_key(&field_name)
+                            && modifier.is_none())
+                            || (is_std_option_type
+                                && config.is_required_by_rules(ident_stack, &field_name));
+                        let is_std_vec_type = super::is_std_vec_type(&field.ty);
 
-                        Item::Struct(mirror_struct)
+                        match (is_required_field, is_std_option_type, is_std_vec_type) {
+                            (true, true, _) => {
+                                let ty = super::maybe_unwrap_option_type(&field.ty);
+                                field.ty = ty.clone();
+                            }
+                            (true, false, _) => {
+                                let ty = &field.ty;
+                                abort!(
+                                    required_fields.get(&field_name).unwrap(),
+                                    format!(
+                                        "Field has type `{}`, which is not an Option<T> type",
+                                        quote!(#ty)
+                                    )
+                                );
+                            }
+                            (_, _, _) => (),
+                        }
+
+                        required_fields.remove(&field_name);
                     }
-                    _ => abort!(
-                        struct_leaf.fqn_ref(),
-                        "Expected struct to have named fields"
-                    ),
+
+                    // the renamed fields are named after the mirror field
+                    // names once the field types are resolved
+                    validate_renamed_fields(item, struct_spec);
+                    for field in &mut fields.named {
+                        let field_name = field.ident.as_ref().unwrap().to_string();
+                        if let Some(renamed) = struct_spec.field_rename(&field_name) {
+                            field.ident = Some(renamed.clone());
+                        }
+                    }
+
+                    if let Some((field_name, field_ident)) = required_fields.into_iter().next() {
+                        abort!(
+                            field_ident,
+                            format!(
+                                "Required field missing from struct definition: {}",
+                                field_name
+                            )
+                        )
+                    }
+
+                    Item::Struct(mirror_struct)
                 }
+                _ => abort!(struct_spec.fqn(), "Expected struct to have named fields"),
             }
-        };
+        }
+    }
+}
 
-        vec![mirror_struct]
+/// Replaces the element type of the field with the mapped one, if any; the
+/// original type must not be resolved yet.
+fn map_field_type(config: &Config, ty: &mut Type, ident_stack: &[String], field_name: &str) {
+    let element_ty = super::field_element_type_mut(ty);
+    if let Some(any_spec) = config.any_spec(ident_stack, field_name) {
+        // the enum is placed next to the struct, the field type is validated
+        // by the enum generator
+        let enum_path = config.this_item_typepath(
+            ident_stack[..ident_stack.len() - 1]
+                .iter()
+                .cloned()
+                .chain([any_spec.enum_ident().to_string()]),
+        );
+        *element_ty = syn::parse_quote!(#enum_path);
+        return;
     }
+    if let Some(mapping) = super::type_mapping(config, element_ty, ident_stack, field_name) {
+        *element_ty = mapping.ty;
+    }
+}
+
+/// The scalar types, that may be required to be non-zero.
+const NON_ZERO_TYPES: [&str; 6] = ["i32", "i64", "u32", "u64", "f32", "f64"];
+
+/// Aborts if the field modifier is not applicable to the original field type.
+fn validate_field_modifier(field_ident: &Ident, ty: &Type, modifier: &FieldModifier) {
+    let (shape, element_ty) = super::field_shape(ty);
+    let element_ident = match element_ty {
+        Type::Path(ty_path) => ty_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    };
+    let is_applicable = match (modifier, shape) {
+        (FieldModifier::NonEmpty, super::FieldShape::Vec | super::FieldShape::HashMap) => true,
+        (FieldModifier::NonEmpty, super::FieldShape::Plain) => {
+            element_ident.as_deref() == Some("String")
+        }
+        (FieldModifier::NonEmptyVec, super::FieldShape::Vec) => true,
+        (FieldModifier::NonZero, super::FieldShape::Plain) => element_ident
+            .as_deref()
+            .is_some_and(|ident| NON_ZERO_TYPES.contains(&ident)),
+        // the oneof type is validated with the variant lookup
+        (FieldModifier::Variant(_), super::FieldShape::Option) => true,
+        (_, _) => false,
+    };
+    if !is_applicable {
+        abort!(
+            field_ident,
+            format!(
+                "Field has type `{}`, which can not be `{}`",
+                quote!(#ty),
+                modifier.name()
+            )
+        );
+    }
+}
+
+/// The scalar types, which default value may be represented as `None`; the
+/// enum fields are `i32`.
+const OPTIONAL_TYPES: [&str; 8] = ["String", "bool", "i32", "i64", "u32", "u64", "f32", "f64"];
+
+/// Aborts if the skipped field is missing from the struct definition.
+fn validate_skipped_fields(item: &ItemStruct, struct_spec: &StructSpec) {
+    for skipped_field in struct_spec.skipped_fields() {
+        if !item
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(skipped_field))
+        {
+            abort!(
+                skipped_field,
+                format!(
+                    "Skipped field missing from struct definition: {}",
+                    skipped_field
+                )
+            );
+        }
+    }
+}
+
+/// Aborts if the renamed field is missing from the struct definition, or its
+/// mirror name conflicts with another field.
+fn validate_renamed_fields(item: &ItemStruct, struct_spec: &StructSpec) {
+    for (renamed_field, renamed) in struct_spec.field_renames() {
+        if !item
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(renamed_field))
+        {
+            abort!(
+                renamed_field,
+                format!(
+                    "Renamed field missing from struct definition: {}",
+                    renamed_field
+                )
+            );
+        }
+        let conflicts = item.fields.iter().any(|field| {
+            let field_ident = field.ident.as_ref().unwrap();
+            field_ident != renamed_field
+                && struct_spec.skip(field).is_none()
+                && struct_spec
+                    .field_rename(&field_ident.to_string())
+                    .unwrap_or(field_ident)
+                    == renamed
+        });
+        if conflicts {
+            abort!(
+                renamed,
+                format!("Field `{}` already exists in the mirror struct", renamed)
+            );
+        }
+    }
+}
+
+/// Aborts if the `with_optional` field is missing from the struct definition.
+fn validate_optional_fields(config: &Config, item: &ItemStruct, ident_stack: &[String]) {
+    for optional_field in config.optional_fields(ident_stack) {
+        if !item
+            .fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(optional_field))
+        {
+            abort!(
+                optional_field,
+                format!(
+                    "Optional field missing from struct definition: {}",
+                    optional_field
+                )
+            );
+        }
+    }
+}
+
+/// Returns `true` if the field is specified with `with_optional`; aborts if
+/// the original field type is not a scalar type.
+pub(crate) fn is_optional_field(
+    config: &Config,
+    ty: &Type,
+    ident_stack: &[String],
+    field_name: &str,
+) -> bool {
+    let Some(optional_field) = config.optional_field(ident_stack, field_name) else {
+        return false;
+    };
+    let is_scalar = match ty {
+        Type::Path(ty_path) => ty_path.path.segments.last().is_some_and(|segment| {
+            segment.arguments.is_empty()
+                && OPTIONAL_TYPES.contains(&segment.ident.to_string().as_str())
+        }),
+        _ => false,
+    };
+    if !is_scalar {
+        abort!(
+            optional_field,
+            format!(
+                "Field has type `{}`, which default value can not be represented as `None`",
+                quote!(#ty)
+            )
+        );
+    }
+    true
+}
+
+fn wrap_option_type(ty: &mut Type) {
+    let inner_ty = ty.clone();
+    *ty = syn::parse_quote!(::core::option::Option<#inner_ty>);
 }
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use proc_macro_error::abort;
 use quote::quote;
 use strfmt::strfmt;
@@ -8,7 +6,11 @@ use syn::Item;
 use syn::ItemImpl;
 use syn::ItemStruct;
 
+use crate::include::spec_tree::FieldDefault;
+use crate::include::spec_tree::FieldModifier;
+use crate::include::spec_tree::FieldSkip;
 use crate::include::spec_tree::SpecTreeLeaf;
+use crate::include::spec_tree::StructSpec;
 use crate::include::Config;
 use crate::traverse::Traverse;
 
@@ -18,48 +20,141 @@ impl Traverse for StructImpl {
     type Item = ItemStruct;
 
     fn traverse(config: &Config, item: &Self::Item, ident_stack: &mut Vec<String>) -> Vec<Item> {
-        let mut vec = Vec::with_capacity(2);
-        vec.extend(generate_try_from_original(config, item, ident_stack));
-        vec.extend(generate_into_original(config, item, ident_stack));
-        vec
+        let struct_spec = match config.spec_tree.get_leaf(ident_stack.iter()) {
+            Some(_struct_leaf @ SpecTreeLeaf::Struct(struct_spec)) => Some(struct_spec.as_ref()),
+            Some(enum_leaf @ SpecTreeLeaf::Enum { .. }) => abort!(
+                enum_leaf.fqn_ref(),
+                "Expected specified item to be enum, but struct found"
+            ),
+            None => None,
+        };
+        generate_impls(config, item, ident_stack, struct_spec)
     }
 }
 
+/// Returns the `TryFrom` and `Into` implementations for the mirror struct,
+/// named after the `item` ident, which differs from the original one for the
+/// named view; the struct with private fields has its constructor and field
+/// accessors implemented as well, the struct with builder has its builder.
+pub(crate) fn generate_impls(
+    config: &Config,
+    item: &ItemStruct,
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
+) -> Vec<Item> {
+    let mut vec = Vec::with_capacity(4);
+    vec.extend(generate_try_from_original(
+        config,
+        item,
+        ident_stack,
+        struct_spec,
+    ));
+    vec.extend(generate_into_original(
+        config,
+        item,
+        ident_stack,
+        struct_spec,
+    ));
+    vec.extend(super::item_private::generate_impl(
+        config,
+        item,
+        ident_stack,
+        struct_spec,
+    ));
+    vec.extend(super::item_builder::generate_builder(
+        config,
+        item,
+        ident_stack,
+        struct_spec,
+    ));
+    vec
+}
+
+// the conversions access the `#[deprecated]` fields as any other ones
 const IMPL_BLOCK_TRY_FROM_ORIGINAL_HEADER: &str = r#"
+    #[allow(deprecated)]
     impl std::convert::TryFrom<{orig_item_typepath}> for {struct_name} {{
         type Error = {error_typepath};
 
         fn try_from(value: {orig_item_typepath}) -> Result<Self, Self::Error> {{
-            Ok(Self {{
 "#;
-const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_AS_IS: &str = "{field_name}: value.{field_name},";
-const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_CONVERTED: &str =
-    "{field_name}: {convert_function_path}(value.{field_name})?,";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_NON_EMPTY: &str = r#"
+    if value.{field_name}.is_empty() {{
+        return Err(Self::Error::new("field is empty").at("{field_name}"));
+    }}
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_NON_ZERO: &str = r#"
+    if value.{field_name} == <{field_ty} as Default>::default() {{
+        return Err(Self::Error::new("field is zero").at("{field_name}"));
+    }}
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_SKIPPED: &str = r#"
+    if !{is_default_function_path}(&value.{field_name}) {{
+        return Err(Self::Error::new("field must not be set").at("{field_name}"));
+    }}
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_BODY: &str = "Ok(Self {";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATED_BODY: &str = "let mirror = Self {";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATE: &str = r#"
+    {validator}(&{value}).map_err(Self::Error::custom)?;
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD: &str = "{field_name}: {field_value},";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_AS_IS: &str = "value.{field_name}";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_CONVERTED: &str = r#"
+    {convert_function_path}(value.{field_name})
+        .map_err(|e| Self::Error::nested("{field_name}", e))?
+"#;
 const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED: &str = r#"
-    {field_name}: value
-        .{field_name}.ok_or(Self::Error {{ reason: "{mirror_struct_fqn}.{field_name} is required" }})?
-        .try_into()?,
+    {unwrapped_value}
+        .try_into()
+        .map_err(|e| Self::Error::nested("{field_name}", e))?
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_MAPPED: &str = r#"
+    {convert_expr}
+        .map_err(|e| Self::Error::nested("{field_name}", e))?
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_OPTIONAL_VALUE: &str =
+    "Some(value.{field_name}).filter(|value| *value != <{field_ty} as Default>::default())";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_NON_EMPTY_VEC: &str = r#"
+    ::prost_unwrap::NonEmpty::new({field_value})
+        .ok_or_else(|| Self::Error::new("field is empty").at("{field_name}"))?
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_VALUE: &str =
+    r#"value.{field_name}.ok_or_else(|| Self::Error::required("{field_name}"))?"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_DEFAULT_VALUE: &str =
+    "value.{field_name}.unwrap_or_default()";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_CALL_VALUE: &str = r#"
+    match value.{field_name} {{
+        Some(value) => value,
+        None => {default_call},
+    }}
+"#;
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_VARIANT: &str = r#"
+    match value.{field_name} {{
+        Some(oneof @ {orig_enum_typepath}::{variant}(_)) => {{
+            match {mirror_enum_typepath}::try_from(oneof)
+                .map_err(|e| Self::Error::nested("{field_name}", e))?
+            {{
+                {mirror_enum_typepath}::{variant}(value) => value,
+                #[allow(unreachable_patterns)]
+                _ => unreachable!(),
+            }}
+        }}
+        #[allow(unreachable_patterns)]
+        Some(_) => return Err(Self::Error::new("expected `{variant}` variant").at("{field_name}")),
+        None => return Err(Self::Error::required("{field_name}")),
+    }}
 "#;
 const IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER: &str = "})}}";
+const IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATED_FOOTER: &str = "}}; {validations} Ok(mirror) }}}}";
 
 fn generate_try_from_original(
     config: &Config,
     item: &ItemStruct,
-    ident_stack: &mut [String],
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
 ) -> Vec<Item> {
-    let mirror_struct_path = ident_stack
-        .iter()
-        .map(ToString::to_string)
-        .collect::<Vec<_>>();
-
-    let required_fields = match config.spec_tree.get_leaf(&mirror_struct_path) {
-        Some(_struct_leaf @ SpecTreeLeaf::Struct(struct_spec)) => struct_spec.fields_map(),
-        Some(enum_leaf @ SpecTreeLeaf::Enum { .. }) => abort!(
-            enum_leaf.fqn_ref(),
-            "Expected specified item to be enum, but struct found"
-        ),
-        None => HashMap::new(),
-    };
+    let required_fields = struct_spec.map(StructSpec::fields_map).unwrap_or_default();
 
     let ret = match item.fields {
         Fields::Named(ref fields) => {
@@ -74,6 +169,29 @@ fn generate_try_from_original(
                 error_typepath => quote!(#error_typepath).to_string()
             )
             .unwrap()];
+            // the original struct validators are called before any conversion,
+            // the mirror struct validators are called once it is built
+            let mut mirror_validations = Vec::new();
+            for validator_spec in config.validator_specs(ident_stack) {
+                let validator = &validator_spec.validator;
+                let validation = strfmt!(
+                    IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATE,
+                    validator => quote!(#validator).to_string(),
+                    value => if validator_spec.original { "value" } else { "mirror" }.to_string()
+                )
+                .unwrap();
+                if validator_spec.original {
+                    try_from_impl.push(validation);
+                } else {
+                    mirror_validations.push(validation);
+                }
+            }
+            let mut try_from_fields = vec![if mirror_validations.is_empty() {
+                IMPL_BLOCK_TRY_FROM_ORIGINAL_BODY
+            } else {
+                IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATED_BODY
+            }
+            .to_string()];
 
             for field in &fields.named {
                 let field_name = field
@@ -81,95 +199,257 @@ fn generate_try_from_original(
                     .as_ref()
                     .expect("Expected field ident to be Some")
                     .to_string();
-                let is_required_field = required_fields.contains_key(&field_name);
-                let is_std_option_type = super::is_std_option_type(&field.ty);
-                let is_std_vec_non_scalar = super::is_std_vec_non_scalar(&field.ty);
-                let is_std_hashmap_non_scalar = super::is_std_hashmap_non_scalar(&field.ty);
-
-                match (
-                    is_required_field,
-                    is_std_option_type,
-                    is_std_vec_non_scalar,
-                    is_std_hashmap_non_scalar,
-                ) {
-                    // field is required, is an Option<T>, unwrap it
-                    (true, true, _, _) => {
+                // the skipped field is discarded, the rejected one is checked
+                // to have the default value
+                match struct_spec.and_then(|spec| spec.skip(field)) {
+                    Some(FieldSkip::Discard) => continue,
+                    Some(FieldSkip::Reject) => {
+                        let is_default_fn_typepath = config.this_item_typepath([
+                            super::items::FUNCTION_NAME_IS_DEFAULT.to_string(),
+                        ]);
                         try_from_impl.push(
                             strfmt!(
-                                IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED,
-                                field_name => field.ident.as_ref().unwrap().to_string(),
-                                mirror_struct_fqn => mirror_struct_path.join("::")
+                                IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_SKIPPED,
+                                field_name => field_name,
+                                is_default_function_path => quote!(#is_default_fn_typepath).to_string()
                             )
                             .unwrap(),
                         );
+                        continue;
                     }
-                    // field is required, but is not an Option<T>, throw an error
-                    (true, false, _, _) => {
-                        let ty = &field.ty;
-                        abort!(
-                            required_fields.get(&field_name).unwrap(),
-                            format!(
-                                "Field has type `{}`, which is not an Option<T> type",
-                                quote!(#ty)
+                    None => (),
+                }
+                // the flattened message is converted into its mirror struct,
+                // which is destructured into the inlined fields
+                if let Some(flattened) =
+                    super::item_flatten::flattened_field(config, field, ident_stack, struct_spec)
+                {
+                    let (prelude, fields) = flattened.destructuring_code();
+                    try_from_impl.push(prelude);
+                    try_from_fields.extend(fields);
+                    continue;
+                }
+                // the listed field with modifier is not an Option<T>, it is
+                // checked instead of being unwrapped
+                let modifier = struct_spec.and_then(|spec| spec.modifier(&field_name));
+                let is_std_option_type = super::is_std_option_type(&field.ty);
+                // the `Option<T>` field marked as `required` by the validation
+                // rules is unwrapped as well
+                let is_required_field = (required_fields.contains_key(&field_name)
+                    && modifier.is_none())
+                    || (is_std_option_type
+                        && config.is_required_by_rules(ident_stack, &field_name));
+                let is_std_vec_non_scalar = super::is_std_vec_non_scalar(&field.ty);
+                let is_std_hashmap_non_scalar = super::is_std_hashmap_non_scalar(&field.ty);
+
+                // field modifier is checked before any conversion, the field
+                // type is validated by the struct generator
+                let check_template = match modifier {
+                    Some(FieldModifier::NonEmpty | FieldModifier::NonEmptyVec) => {
+                        Some(IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_NON_EMPTY)
+                    }
+                    Some(FieldModifier::NonZero) => {
+                        Some(IMPL_BLOCK_TRY_FROM_ORIGINAL_CHECK_NON_ZERO)
+                    }
+                    Some(FieldModifier::Variant(_)) | None => None,
+                };
+                if let Some(check_template) = check_template {
+                    let field_ty = &field.ty;
+                    try_from_impl.push(
+                        strfmt!(
+                            check_template,
+                            field_name => field_name.clone(),
+                            field_ty => quote!(#field_ty).to_string()
+                        )
+                        .unwrap(),
+                    );
+                }
+                try_from_impl.extend(super::field_rules::checks(
+                    config,
+                    ident_stack,
+                    &field_name,
+                    &field.ty,
+                ));
+
+                let (shape, element_ty) = super::field_shape(&field.ty);
+                let mapping = super::type_mapping(config, element_ty, ident_stack, &field_name);
+                let field_value = if let Some(FieldModifier::Variant(variant)) = &modifier {
+                    // the oneof is converted as a whole, the variant is
+                    // extracted from the mirrored enum
+                    let oneof_variant =
+                        super::oneof_variant(config, &field.ty, ident_stack, variant);
+                    let orig_enum_typepath =
+                        config.orig_item_typepath(oneof_variant.enum_stack.iter().cloned());
+                    let mirror_enum_typepath =
+                        config.this_item_typepath(oneof_variant.enum_stack.iter().cloned());
+                    strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_VARIANT,
+                        field_name => field_name.clone(),
+                        variant => variant.to_string(),
+                        orig_enum_typepath => quote!(#orig_enum_typepath).to_string(),
+                        mirror_enum_typepath => quote!(#mirror_enum_typepath).to_string()
+                    )
+                    .unwrap()
+                } else if super::item_struct::is_optional_field(
+                    config,
+                    &field.ty,
+                    ident_stack,
+                    &field_name,
+                ) {
+                    // the default value is mapped into `None`, the field type
+                    // is validated by the struct generator
+                    let field_ty = &field.ty;
+                    let optional_value = strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_OPTIONAL_VALUE,
+                        field_name => field_name.clone(),
+                        field_ty => quote!(#field_ty).to_string()
+                    )
+                    .unwrap();
+                    match mapping {
+                        Some(mapping) => strfmt!(
+                            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_MAPPED,
+                            field_name => field_name.clone(),
+                            convert_expr => super::mapped_try_from_expr(
+                                super::FieldShape::Option,
+                                &optional_value,
+                                &mapping
                             )
-                        );
+                        )
+                        .unwrap(),
+                        None => optional_value,
                     }
-                    // field is not required, but is an Option<T>: convert with a function call
-                    (_, true, _, _) => {
-                        let convert_fn_typepath = config.this_item_typepath(vec![
-                            super::items::FUNCTION_NAME_CONVERT_OPTION_TRY_FROM.to_string(),
-                        ]);
-                        try_from_impl.push(
+                } else if let Some(mapping) = mapping {
+                    // field element type is mapped, convert with mapping functions
+                    let convert_expr = match (is_required_field, shape) {
+                        (true, super::FieldShape::Option) => super::mapped_try_from_expr(
+                            super::FieldShape::Plain,
+                            &unwrapped_value(&field_name, struct_spec),
+                            &mapping,
+                        ),
+                        (true, _) => {
+                            let ty = &field.ty;
+                            abort!(
+                                required_fields.get(&field_name).unwrap(),
+                                format!(
+                                    "Field has type `{}`, which is not an Option<T> type",
+                                    quote!(#ty)
+                                )
+                            );
+                        }
+                        (false, shape) => super::mapped_try_from_expr(
+                            shape,
+                            &format!("value.{field_name}"),
+                            &mapping,
+                        ),
+                    };
+                    strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_MAPPED,
+                        field_name => field_name.clone(),
+                        convert_expr => convert_expr
+                    )
+                    .unwrap()
+                } else {
+                    match (
+                        is_required_field,
+                        is_std_option_type,
+                        is_std_vec_non_scalar,
+                        is_std_hashmap_non_scalar,
+                    ) {
+                        // field is required, is an Option<T>, unwrap it
+                        (true, true, _, _) => strfmt!(
+                            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED,
+                            field_name => field_name.clone(),
+                            unwrapped_value => unwrapped_value(&field_name, struct_spec)
+                        )
+                        .unwrap(),
+                        // field is required, but is not an Option<T>, throw an error
+                        (true, false, _, _) => {
+                            let ty = &field.ty;
+                            abort!(
+                                required_fields.get(&field_name).unwrap(),
+                                format!(
+                                    "Field has type `{}`, which is not an Option<T> type",
+                                    quote!(#ty)
+                                )
+                            );
+                        }
+                        // field is not required, but is an Option<T>: convert with a function call
+                        (_, true, _, _) => {
+                            let convert_fn_typepath = config.this_item_typepath(vec![
+                                super::items::FUNCTION_NAME_CONVERT_OPTION_TRY_FROM.to_string(),
+                            ]);
                             strfmt!(
                                 IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_CONVERTED,
-                                field_name => field_name,
+                                field_name => field_name.clone(),
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
-                            .unwrap(),
-                        );
-                    }
-                    // field is not required, but is a Vec<T> (non-scalar): convert with a function call
-                    (_, _, true, _) => {
-                        let convert_fn_typepath = config.this_item_typepath(vec![
-                            super::items::FUNCTION_NAME_CONVERT_VEC_TRY_FROM.to_string(),
-                        ]);
-                        try_from_impl.push(
+                            .unwrap()
+                        }
+                        // field is not required, but is a Vec<T> (non-scalar): convert with a function call
+                        (_, _, true, _) => {
+                            let convert_fn_typepath = config.this_item_typepath(vec![
+                                super::items::FUNCTION_NAME_CONVERT_VEC_TRY_FROM.to_string(),
+                            ]);
                             strfmt!(
                                 IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_CONVERTED,
-                                field_name => field_name,
+                                field_name => field_name.clone(),
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
-                            .unwrap(),
-                        );
-                    }
-                    // field is not required, but is a HashMap<K, T> (T is non-scalar): convert with a function call
-                    (_, _, _, true) => {
-                        let convert_fn_typepath = config.this_item_typepath(vec![
-                            super::items::FUNCTION_NAME_CONVERT_HASHMAP_TRY_FROM.to_string(),
-                        ]);
-                        try_from_impl.push(
+                            .unwrap()
+                        }
+                        // field is not required, but is a HashMap<K, T> (T is non-scalar): convert with a function call
+                        (_, _, _, true) => {
+                            let convert_fn_typepath = config.this_item_typepath(vec![
+                                super::items::FUNCTION_NAME_CONVERT_HASHMAP_TRY_FROM.to_string(),
+                            ]);
                             strfmt!(
                                 IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_CONVERTED,
-                                field_name => field_name,
+                                field_name => field_name.clone(),
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
-                            .unwrap(),
-                        );
-                    }
-                    // field is not required, not an Option<T> nor Vec<T>: pass as is
-                    (_, _, _, _) => {
-                        try_from_impl.push(
-                            strfmt!(
-                                IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_AS_IS,
-                                field_name => field_name
-                            )
-                            .unwrap(),
-                        );
+                            .unwrap()
+                        }
+                        // field is not required, not an Option<T> nor Vec<T>: pass as is
+                        (_, _, _, _) => strfmt!(
+                            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_AS_IS,
+                            field_name => field_name.clone()
+                        )
+                        .unwrap(),
                     }
-                }
+                };
+
+                // non-empty repeated field is wrapped into the runtime type
+                let field_value = match modifier {
+                    Some(FieldModifier::NonEmptyVec) => strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_NON_EMPTY_VEC,
+                        field_name => field_name.clone(),
+                        field_value => field_value
+                    )
+                    .unwrap(),
+                    _ => field_value,
+                };
+                try_from_fields.push(
+                    strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD,
+                        field_name => mirror_field_name(&field_name, struct_spec),
+                        field_value => field_value
+                    )
+                    .unwrap(),
+                );
             }
 
-            try_from_impl.push(IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER.to_string());
+            try_from_impl.extend(try_from_fields);
+            if mirror_validations.is_empty() {
+                try_from_impl.push(IMPL_BLOCK_TRY_FROM_ORIGINAL_FOOTER.to_string());
+            } else {
+                try_from_impl.push(
+                    strfmt!(
+                        IMPL_BLOCK_TRY_FROM_ORIGINAL_VALIDATED_FOOTER,
+                        validations => mirror_validations.join("")
+                    )
+                    .unwrap(),
+                );
+            }
             let try_from_impl_block: ItemImpl =
                 syn::parse_str(try_from_impl.join("").as_str()).unwrap();
 
@@ -181,36 +461,64 @@ fn generate_try_from_original(
     ret
 }
 
+/// Returns the mirror field name, which differs from the original one for the
+/// renamed field.
+fn mirror_field_name(field_name: &str, struct_spec: Option<&StructSpec>) -> String {
+    struct_spec
+        .and_then(|spec| spec.field_rename(field_name))
+        .map(ToString::to_string)
+        .unwrap_or_else(|| field_name.to_string())
+}
+
+/// Returns the expression unwrapping the required field value, either failing
+/// or falling back to the default value if the field is missing.
+fn unwrapped_value(field_name: &str, struct_spec: Option<&StructSpec>) -> String {
+    match struct_spec.and_then(|spec| spec.default(field_name)) {
+        None => strfmt!(
+            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_VALUE,
+            field_name => field_name.to_string()
+        )
+        .unwrap(),
+        Some(FieldDefault::Default) => strfmt!(
+            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_DEFAULT_VALUE,
+            field_name => field_name.to_string()
+        )
+        .unwrap(),
+        Some(FieldDefault::Call(call_expr)) => strfmt!(
+            IMPL_BLOCK_TRY_FROM_ORIGINAL_FIELD_UNWRAPPED_CALL_VALUE,
+            field_name => field_name.to_string(),
+            default_call => quote!(#call_expr).to_string()
+        )
+        .unwrap(),
+    }
+}
+
 const IMPL_BLOCK_INTO_ORIGINAL_HEADER: &str = r#"
+    #[allow(deprecated)]
     impl std::convert::Into<{orig_item_typepath}> for {struct_name} {{
         fn into(self) -> {orig_item_typepath} {{
             {orig_item_typepath} {{
 "#;
-const IMPL_BLOCK_INTO_ORIGINAL_FIELD_AS_IS: &str = "{field_name}: self.{field_name}.into(),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_AS_IS: &str = "{field_name}: {value}.into(),";
 const IMPL_BLOCK_INTO_ORIGINAL_FIELD_CONVERTED: &str =
-    "{field_name}: {convert_function_path}(self.{field_name}),";
-const IMPL_BLOCK_INTO_ORIGINAL_FIELD_WRAPPED: &str =
-    "{field_name}: Some(self.{field_name}.into()),";
+    "{field_name}: {convert_function_path}({value}),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_WRAPPED: &str = "{field_name}: Some({value}.into()),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_MAPPED: &str = "{field_name}: {convert_expr},";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_MAPPED_WRAPPED: &str = "{field_name}: Some({convert_expr}),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_OPTIONAL: &str =
+    "{field_name}: {convert_expr}.unwrap_or_default(),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_SKIPPED: &str = "{field_name}: Default::default(),";
+const IMPL_BLOCK_INTO_ORIGINAL_FIELD_VARIANT: &str =
+    "{field_name}: Some({mirror_enum_typepath}::{variant}({value}).into()),";
 const IMPL_BLOCK_INTO_ORIGINAL_FOOTER: &str = "}}}";
 
 fn generate_into_original(
     config: &Config,
     item: &ItemStruct,
-    ident_stack: &mut [String],
+    ident_stack: &[String],
+    struct_spec: Option<&StructSpec>,
 ) -> Vec<Item> {
-    let mirror_struct_path = ident_stack
-        .iter()
-        .map(ToString::to_string)
-        .collect::<Vec<_>>();
-
-    let required_fields = match config.spec_tree.get_leaf(&mirror_struct_path) {
-        Some(_struct_leaf @ SpecTreeLeaf::Struct(struct_spec)) => struct_spec.fields_map(),
-        Some(enum_leaf @ SpecTreeLeaf::Enum { .. }) => abort!(
-            enum_leaf.fqn_ref(),
-            "Expected specified item to be enum, but struct found"
-        ),
-        None => HashMap::new(),
-    };
+    let required_fields = struct_spec.map(StructSpec::fields_map).unwrap_or_default();
 
     let ret = match item.fields {
         Fields::Named(ref fields) => {
@@ -229,10 +537,106 @@ fn generate_into_original(
                     .as_ref()
                     .expect("Expected field ident to be Some")
                     .to_string();
-                let is_required_field = required_fields.contains_key(&field_name);
+                // the skipped field has the default value
+                if struct_spec.and_then(|spec| spec.skip(field)).is_some() {
+                    try_from_impl.push(
+                        strfmt!(
+                            IMPL_BLOCK_INTO_ORIGINAL_FIELD_SKIPPED,
+                            field_name => field_name
+                        )
+                        .unwrap(),
+                    );
+                    continue;
+                }
+                // the flattened message is rebuilt from the inlined fields
+                if let Some(flattened) =
+                    super::item_flatten::flattened_field(config, field, ident_stack, struct_spec)
+                {
+                    try_from_impl.push(flattened.rebuilding_code());
+                    continue;
+                }
+                // the listed field with modifier is not an Option<T>, it is
+                // checked instead of being unwrapped
+                let modifier = struct_spec.and_then(|spec| spec.modifier(&field_name));
                 let is_std_option_type = super::is_std_option_type(&field.ty);
+                // the `Option<T>` field marked as `required` by the validation
+                // rules is unwrapped as well
+                let is_required_field = (required_fields.contains_key(&field_name)
+                    && modifier.is_none())
+                    || (is_std_option_type
+                        && config.is_required_by_rules(ident_stack, &field_name));
                 let is_std_vec_non_scalar = super::is_std_vec_non_scalar(&field.ty);
                 let is_std_hashmap_non_scalar = super::is_std_hashmap_non_scalar(&field.ty);
+                // non-empty repeated field is unwrapped from the runtime type
+                let mirror_field_name = mirror_field_name(&field_name, struct_spec);
+                let value = match modifier {
+                    Some(FieldModifier::NonEmptyVec) => {
+                        format!("self.{mirror_field_name}.into_inner()")
+                    }
+                    _ => format!("self.{mirror_field_name}"),
+                };
+
+                let (shape, element_ty) = super::field_shape(&field.ty);
+                let mapping = super::type_mapping(config, element_ty, ident_stack, &field_name);
+                if let Some(FieldModifier::Variant(variant)) = &modifier {
+                    // the variant is wrapped back into the mirrored enum
+                    let oneof_variant =
+                        super::oneof_variant(config, &field.ty, ident_stack, variant);
+                    let mirror_enum_typepath = config.this_item_typepath(oneof_variant.enum_stack);
+                    try_from_impl.push(
+                        strfmt!(
+                            IMPL_BLOCK_INTO_ORIGINAL_FIELD_VARIANT,
+                            field_name => field_name,
+                            variant => variant.to_string(),
+                            value => value,
+                            mirror_enum_typepath => quote!(#mirror_enum_typepath).to_string()
+                        )
+                        .unwrap(),
+                    );
+                    continue;
+                }
+                if config.optional_field(ident_stack, &field_name).is_some() {
+                    // `None` is mapped back into the default value
+                    let convert_expr = match mapping {
+                        Some(mapping) => {
+                            super::mapped_into_expr(super::FieldShape::Option, &value, &mapping)
+                        }
+                        None => value,
+                    };
+                    try_from_impl.push(
+                        strfmt!(
+                            IMPL_BLOCK_INTO_ORIGINAL_FIELD_OPTIONAL,
+                            field_name => field_name,
+                            convert_expr => convert_expr
+                        )
+                        .unwrap(),
+                    );
+                    continue;
+                }
+                if let Some(mapping) = mapping {
+                    // field element type is mapped, convert with mapping functions;
+                    // required field type is validated by the TryFrom generator
+                    let (template, shape) = match (is_required_field, shape) {
+                        (true, _) => (
+                            IMPL_BLOCK_INTO_ORIGINAL_FIELD_MAPPED_WRAPPED,
+                            super::FieldShape::Plain,
+                        ),
+                        (false, shape) => (IMPL_BLOCK_INTO_ORIGINAL_FIELD_MAPPED, shape),
+                    };
+                    try_from_impl.push(
+                        strfmt!(
+                            template,
+                            field_name => field_name.clone(),
+                            convert_expr => super::mapped_into_expr(
+                                shape,
+                                &value,
+                                &mapping
+                            )
+                        )
+                        .unwrap(),
+                    );
+                    continue;
+                }
 
                 match (
                     is_required_field,
@@ -245,7 +649,8 @@ fn generate_into_original(
                         try_from_impl.push(
                             strfmt!(
                                 IMPL_BLOCK_INTO_ORIGINAL_FIELD_WRAPPED,
-                                field_name => field.ident.as_ref().unwrap().to_string()
+                                field_name => field_name,
+                                value => value
                             )
                             .unwrap(),
                         );
@@ -270,6 +675,7 @@ fn generate_into_original(
                             strfmt!(
                                 IMPL_BLOCK_INTO_ORIGINAL_FIELD_CONVERTED,
                                 field_name => field_name,
+                                value => value,
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
                             .unwrap(),
@@ -284,6 +690,7 @@ fn generate_into_original(
                             strfmt!(
                                 IMPL_BLOCK_INTO_ORIGINAL_FIELD_CONVERTED,
                                 field_name => field_name,
+                                value => value,
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
                             .unwrap(),
@@ -298,6 +705,7 @@ fn generate_into_original(
                             strfmt!(
                                 IMPL_BLOCK_INTO_ORIGINAL_FIELD_CONVERTED,
                                 field_name => field_name,
+                                value => value,
                                 convert_function_path => quote!(#convert_fn_typepath).to_string()
                             )
                             .unwrap(),
@@ -308,7 +716,8 @@ fn generate_into_original(
                         try_from_impl.push(
                             strfmt!(
                                 IMPL_BLOCK_INTO_ORIGINAL_FIELD_AS_IS,
-                                field_name => field_name
+                                field_name => field_name,
+                                value => value
                             )
                             .unwrap(),
                         );
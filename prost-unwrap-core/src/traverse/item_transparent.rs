@@ -0,0 +1,149 @@
+use proc_macro_error::abort;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Field;
+use syn::Fields;
+use syn::Item;
+use syn::Path;
+use syn::Type;
+
+use super::TypeMapping;
+use crate::include::Config;
+use crate::traverse::Traverse;
+
+const TRY_FROM_FN: &str = r#"
+    |value: {orig_typepath}| {mirror_typepath}::try_from(value).map(|value| value.{field_name})
+"#;
+const INTO_FN: &str = r#"
+    |value| Into::<{orig_typepath}>::into({mirror_typepath} {{ {field_name}: value }})
+"#;
+
+/// Returns the mapping of the wrapper message, specified with
+/// `with_transparent`, into its single field type; the wrapper is converted
+/// with its mirror struct, so the field type is the mirrored one.
+pub(crate) fn type_mapping(
+    config: &Config,
+    ty: &Type,
+    ident_stack: &[String],
+) -> Option<TypeMapping> {
+    let Type::Path(ty_path) = ty else {
+        return None;
+    };
+    let mut wrapper_stack = super::source_item_path(config, ty_path, ident_stack)?;
+    let fqn = config.transparent_spec(&wrapper_stack)?;
+    let (item_struct, _) = wrapper_field(config, fqn);
+
+    // the mirror field may be renamed
+    let mirror_field =
+        match super::item_struct::Struct::traverse(config, item_struct, &mut wrapper_stack).pop() {
+            Some(Item::Struct(mirror_struct)) => mirror_struct
+                .fields
+                .into_iter()
+                .next()
+                .expect("Expected mirror wrapper struct to have a field"),
+            _ => unreachable!("Expected wrapper struct to be mirrored as struct"),
+        };
+    let field_name = mirror_field.ident.as_ref().unwrap().to_string();
+    let mirror_ty = mirror_field.ty;
+
+    let orig_typepath = config.orig_item_typepath(wrapper_stack.iter().cloned());
+    let mirror_typepath = config.this_item_typepath(wrapper_stack.iter().cloned());
+    let try_from_fn = strfmt!(
+        TRY_FROM_FN,
+        orig_typepath => quote!(#orig_typepath).to_string(),
+        mirror_typepath => quote!(#mirror_typepath).to_string(),
+        field_name => field_name.clone()
+    )
+    .unwrap();
+    let into_fn = strfmt!(
+        INTO_FN,
+        orig_typepath => quote!(#orig_typepath).to_string(),
+        mirror_typepath => quote!(#mirror_typepath).to_string(),
+        field_name => field_name
+    )
+    .unwrap();
+
+    Some(TypeMapping {
+        ty: mirror_ty,
+        try_from_fn: syn::parse_str(&try_from_fn).expect("Expected wrapper conversion closure"),
+        into_fn: syn::parse_str(&into_fn).expect("Expected wrapper conversion closure"),
+    })
+}
+
+/// Aborts if any of the `with_transparent` structs is not a single field
+/// struct of the linked source code, or its field refers back to the struct,
+/// directly or through another transparent struct, so it would be mapped
+/// endlessly.
+pub(crate) fn validate_specs(config: &Config) {
+    for fqn in &config.transparents {
+        wrapper_field(config, fqn);
+    }
+
+    for fqn in &config.transparents {
+        let mut visited: Vec<Vec<String>> = Vec::new();
+        let mut wrapper_fqn = fqn;
+        loop {
+            let wrapper_stack: Vec<String> = wrapper_fqn
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            if visited.contains(&wrapper_stack) {
+                abort!(
+                    fqn,
+                    format!(
+                        "Transparent struct refers back to itself: {}",
+                        visited
+                            .iter()
+                            .chain([&wrapper_stack])
+                            .map(|stack| stack.join("::"))
+                            .collect::<Vec<_>>()
+                            .join(" -> ")
+                    )
+                );
+            }
+            let (_, field) = wrapper_field(config, wrapper_fqn);
+            let next_fqn = match super::field_shape(&field.ty) {
+                (_, Type::Path(ty_path)) => {
+                    super::source_item_path(config, ty_path, &wrapper_stack)
+                        .and_then(|field_stack| config.transparent_spec(&field_stack))
+                }
+                _ => None,
+            };
+            visited.push(wrapper_stack);
+            match next_fqn {
+                Some(next_fqn) => wrapper_fqn = next_fqn,
+                None => break,
+            }
+        }
+    }
+}
+
+/// Returns the wrapper struct and its single field; aborts if the struct is
+/// missing from the linked source code or has multiple fields.
+fn wrapper_field<'a>(config: &'a Config, fqn: &Path) -> (&'a syn::ItemStruct, &'a Field) {
+    let ident_path: Vec<String> = fqn
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let Some(Item::Struct(item_struct)) = super::find_item(&config.source.ast.items, &ident_path)
+    else {
+        abort!(
+            fqn,
+            format!(
+                "Struct `{}` not found in the linked source code",
+                ident_path.join("::")
+            )
+        );
+    };
+    match item_struct.fields {
+        Fields::Named(ref fields) if fields.named.len() == 1 => {
+            (item_struct, fields.named.first().unwrap())
+        }
+        _ => abort!(
+            fqn,
+            "Expected struct to have a single field to be transparent"
+        ),
+    }
+}
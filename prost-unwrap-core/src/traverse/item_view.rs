@@ -0,0 +1,101 @@
+use proc_macro_error::abort;
+use quote::quote;
+use syn::Ident;
+use syn::Item;
+use syn::ItemStruct;
+use syn::Type;
+
+use crate::include::Config;
+use crate::traverse::Traverse;
+
+/// Generates the named views of the struct, specified with
+/// `with_struct(Struct as View, [...])`; the view is the mirror struct with its
+/// own required fields, converted from and into the same original struct.
+pub struct View;
+
+impl Traverse for View {
+    type Item = ItemStruct;
+
+    fn traverse(config: &Config, item: &Self::Item, ident_stack: &mut Vec<String>) -> Vec<Item> {
+        let mut vec = Vec::new();
+        for view_spec in config.spec_tree.get_views(ident_stack.iter()) {
+            let view = view_spec.view().expect("Expected view spec to have a view");
+
+            // the view is placed next to the struct
+            let view_stack: Vec<String> = ident_stack[..ident_stack.len() - 1]
+                .iter()
+                .cloned()
+                .chain([view.to_string()])
+                .collect();
+            if super::find_item(&config.source.ast.items, &view_stack).is_some() {
+                abort!(
+                    view,
+                    format!(
+                        "View name conflicts with the item of the linked source code: {}",
+                        view_stack.join("::")
+                    )
+                );
+            }
+
+            let mut view_item = item.clone();
+            view_item.ident = view.clone();
+            vec.push(super::item_struct::mirror_struct(
+                config,
+                &view_item,
+                ident_stack,
+                Some(view_spec),
+            ));
+            vec.extend(super::item_struct_impl::generate_impls(
+                config,
+                &view_item,
+                ident_stack,
+                Some(view_spec),
+            ));
+        }
+        vec
+    }
+}
+
+/// Replaces the element type of the mirror field with the named view of the
+/// original struct, e.g. `user as UserForCreate`; aborts if the original
+/// element type has no such view.
+pub(crate) fn apply_field_view(
+    config: &Config,
+    ty: &mut Type,
+    orig_ty: &Type,
+    ident_stack: &[String],
+    field_name: &str,
+    field_view: &Ident,
+) {
+    let (_, orig_element_ty) = super::field_shape(orig_ty);
+    let struct_path = match orig_element_ty {
+        Type::Path(ty_path)
+            if super::type_mapping(config, orig_element_ty, ident_stack, field_name).is_none() =>
+        {
+            super::source_item_path(config, ty_path, ident_stack)
+        }
+        _ => None,
+    };
+    let has_view = struct_path.as_ref().is_some_and(|struct_path| {
+        config
+            .spec_tree
+            .get_views(struct_path.iter())
+            .iter()
+            .any(|view_spec| view_spec.view() == Some(field_view))
+    });
+    if !has_view {
+        abort!(
+            field_view,
+            format!(
+                "Field has type `{}`, which has no `{}` named view in the linked source code",
+                quote!(#orig_ty),
+                field_view
+            )
+        );
+    }
+
+    // the view is placed next to the struct, so only the ident differs
+    if let Type::Path(ty_path) = super::field_element_type_mut(ty) {
+        ty_path.path.segments.last_mut().unwrap().ident = field_view.clone();
+    }
+}
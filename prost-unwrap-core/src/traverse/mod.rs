@@ -1,10 +1,15 @@
 use proc_macro_error::abort;
 use quote::quote;
+use strfmt::strfmt;
 use syn::punctuated::Punctuated;
 use syn::Attribute;
+use syn::Expr;
 use syn::File;
 use syn::GenericArgument;
+use syn::Ident;
 use syn::Item;
+use syn::ItemEnum;
+use syn::ItemStruct;
 use syn::Meta;
 use syn::PathArguments;
 use syn::Token;
@@ -12,12 +17,24 @@ use syn::Type;
 use syn::TypePath;
 
 use crate::include::Config;
+use crate::include::CustomType;
+use crate::include::TypeMappingSpec;
 
+mod field_rules;
+mod item_any;
+mod item_builder;
 mod item_enum;
 mod item_enum_impl;
+mod item_flatten;
 mod item_mod;
+mod item_newtype;
+mod item_oneof_enum;
+mod item_private;
 mod item_struct;
 mod item_struct_impl;
+mod item_transparent;
+mod item_view;
+mod well_known_types;
 
 pub trait Traverse {
     type Item;
@@ -36,6 +53,12 @@ pub(crate) fn copy_unwrapped(config: &Config) -> File {
     items.extend(items::item_convert_vec_into());
     items.extend(items::item_convert_hashmap_try_from());
     items.extend(items::item_convert_hashmap_into());
+    items.extend(items::item_is_default());
+    items.extend(well_known_types::items(config));
+    items.extend(item_newtype::items(config));
+    item_transparent::validate_specs(config);
+    item_private::validate_specs(config);
+    item_builder::validate_specs(config);
     items.extend(copy_unwrapped_items(config, &mut ident_stack, &ast.items));
 
     File {
@@ -57,6 +80,28 @@ fn copy_unwrapped_items<'a, I: IntoIterator<Item = &'a Item>>(
             Item::Struct(item_struct) => {
                 let mut items = Vec::new();
                 ident_stack.push(item_struct.ident.to_string());
+                // the renamed struct is mirrored under its new name
+                let renamed_struct;
+                let item_struct = match config.renamed_ident(ident_stack) {
+                    Some(ident) => {
+                        renamed_struct = ItemStruct {
+                            ident: ident.clone(),
+                            ..item_struct.clone()
+                        };
+                        &renamed_struct
+                    }
+                    None => item_struct,
+                };
+                if config.oneof_enum_spec(ident_stack).is_some() {
+                    items.extend(item_oneof_enum::OneofEnum::traverse(
+                        config,
+                        item_struct,
+                        ident_stack,
+                    ));
+                    ident_stack.pop();
+                    ret_items.extend(items);
+                    continue;
+                }
                 items.extend(item_struct::Struct::traverse(
                     config,
                     item_struct,
@@ -67,12 +112,30 @@ fn copy_unwrapped_items<'a, I: IntoIterator<Item = &'a Item>>(
                     item_struct,
                     ident_stack,
                 ));
+                items.extend(item_any::AnyEnum::traverse(
+                    config,
+                    item_struct,
+                    ident_stack,
+                ));
+                items.extend(item_view::View::traverse(config, item_struct, ident_stack));
                 ident_stack.pop();
                 items
             }
             Item::Enum(item_enum) => {
                 let mut items = Vec::new();
                 ident_stack.push(item_enum.ident.to_string());
+                // the renamed enum is mirrored under its new name
+                let renamed_enum;
+                let item_enum = match config.renamed_ident(ident_stack) {
+                    Some(ident) => {
+                        renamed_enum = ItemEnum {
+                            ident: ident.clone(),
+                            ..item_enum.clone()
+                        };
+                        &renamed_enum
+                    }
+                    None => item_enum,
+                };
                 items.extend(item_enum::Enum::traverse(config, item_enum, ident_stack));
                 items.extend(item_enum_impl::EnumImpl::traverse(
                     config,
@@ -125,6 +188,505 @@ fn drop_prost_attributes(attrs: &mut Vec<Attribute>) {
     attrs.retain(|attr| !attr.path().is_ident("prost"));
 }
 
+/// Rewrites the type paths, including the generic arguments, into the absolute
+/// paths of mirrored items:
+/// - relative (`super::`-prefixed) paths, prost refers to the items of other
+///   packages this way, e.g. `super::super::root::MsgA`;
+/// - absolute paths of prost `extern_path` items, specified with `with_extern`.
+///
+/// The `ident_stack` is the path of the item owning the type, relative to the
+/// linked source module.
+pub(crate) fn resolve_type_paths(
+    config: &Config,
+    ty: &mut Type,
+    ident_stack: &[String],
+    field_name: &str,
+) {
+    let Type::Path(ty_path) = ty else {
+        return;
+    };
+
+    for segment in ty_path.path.segments.iter_mut() {
+        if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+            for arg in args.args.iter_mut() {
+                if let GenericArgument::Type(ref mut arg_ty) = arg {
+                    resolve_type_paths(config, arg_ty, ident_stack, field_name);
+                }
+            }
+        }
+    }
+
+    if ty_path.qself.is_some() {
+        return;
+    }
+
+    if let Some(path) = config.extern_item_typepath(&ty_path.path) {
+        ty_path.path = path;
+        return;
+    }
+
+    if ty_path.path.leading_colon.is_some() {
+        return;
+    }
+
+    // prost refers to the items of the same module by the relative path, which
+    // is the same in the mirror module, unless the item is renamed
+    if ty_path.path.segments.first().unwrap().ident != "super" {
+        if let Some(item_path) = source_item_path(config, ty_path, ident_stack) {
+            if config.renamed_ident(&item_path).is_some() {
+                let mut path = config.this_item_typepath(item_path);
+                path.segments.last_mut().unwrap().arguments =
+                    ty_path.path.segments.last().unwrap().arguments.clone();
+                ty_path.path = path;
+            }
+        }
+        return;
+    }
+
+    let Some(ident_path) = relative_item_path(config, ty_path, ident_stack) else {
+        abort!(
+            config.source.path_span,
+            format!(
+                "Field `{}.{}` type `{}` refers outside of `with_original_mod` module",
+                ident_stack.join("::"),
+                field_name,
+                quote!(#ty_path)
+            )
+        );
+    };
+
+    match config.mirror_item_typepath(&ident_path) {
+        Some(mut path) => {
+            path.segments.last_mut().unwrap().arguments =
+                ty_path.path.segments.last().unwrap().arguments.clone();
+            ty_path.path = path;
+        }
+        None => {
+            let item_path = ident_path.join("::");
+            abort!(
+                config.source.path_span,
+                format!(
+                    "Field `{}.{}` refers to `{}`, which is not mirrored by this macro call; \
+                    use `with_foreign_mod` parameter to specify the module mirroring its package, \
+                    e.g. `with_foreign_mod({}, crate::path::to::mirror)`",
+                    ident_stack.join("::"),
+                    field_name,
+                    item_path,
+                    ident_path[..ident_path.len() - 1].join("::")
+                )
+            );
+        }
+    }
+}
+
+/// Returns the item path relative to `with_original_mod` argument for the
+/// relative (`super::`-prefixed) type path, e.g. `root::MsgA`; returns `None`
+/// if the path refers outside of the `with_original_mod` module.
+pub(crate) fn relative_item_path(
+    config: &Config,
+    ty_path: &TypePath,
+    ident_stack: &[String],
+) -> Option<Vec<String>> {
+    // the item module path, relative to `with_original_mod` argument
+    let mut ident_path: Vec<String> = config
+        .source
+        .fqn
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .chain(ident_stack[..ident_stack.len() - 1].iter().cloned())
+        .collect();
+
+    let mut segments = ty_path.path.segments.iter().peekable();
+    while segments
+        .next_if(|segment| segment.ident == "super")
+        .is_some()
+    {
+        ident_path.pop()?;
+    }
+    ident_path.extend(segments.map(|segment| segment.ident.to_string()));
+
+    Some(ident_path)
+}
+
+/// Returns the item path relative to the linked source module for the type
+/// path of the item within the linked source code, e.g. `OrderId` or
+/// `super::OrderId`; returns `None` for the other type paths.
+pub(crate) fn source_item_path(
+    config: &Config,
+    ty_path: &TypePath,
+    ident_stack: &[String],
+) -> Option<Vec<String>> {
+    // prost refers to the items of the same module by the relative path
+    if ty_path.qself.is_some() || ty_path.path.leading_colon.is_some() {
+        return None;
+    }
+
+    let source_path: Vec<String> = config
+        .source
+        .fqn
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let ident_path = relative_item_path(config, ty_path, ident_stack)?;
+    ident_path
+        .strip_prefix(source_path.as_slice())
+        .map(<[String]>::to_vec)
+}
+
+/// The field type wrapping the element type, see `field_shape`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FieldShape {
+    /// `T`, e.g. oneof variant or scalar field
+    Plain,
+    /// `Option<T>`, optional field
+    Option,
+    /// `Vec<T>`, repeated field
+    Vec,
+    /// `HashMap<K, T>`, map field
+    HashMap,
+}
+
+/// Returns the field shape and the element type, e.g. `(FieldShape::Vec, T)`
+/// for `Vec<T>`.
+pub(crate) fn field_shape(ty: &Type) -> (FieldShape, &Type) {
+    if let Type::Path(ty_path) = ty {
+        if let Some(segment) = ty_path.path.segments.last() {
+            let shape = match segment.ident.to_string().as_str() {
+                "Option" => FieldShape::Option,
+                "Vec" => FieldShape::Vec,
+                "HashMap" => FieldShape::HashMap,
+                _ => return (FieldShape::Plain, ty),
+            };
+            if let PathArguments::AngleBracketed(ref args) = segment.arguments {
+                if let Some(GenericArgument::Type(element_ty)) = args.args.last() {
+                    return (shape, element_ty);
+                }
+            }
+        }
+    }
+    (FieldShape::Plain, ty)
+}
+
+/// Mutable counterpart of `field_shape`, returns the element type only.
+pub(crate) fn field_element_type_mut(ty: &mut Type) -> &mut Type {
+    if field_shape(ty).0 == FieldShape::Plain {
+        return ty;
+    }
+    if let Type::Path(ty_path) = ty {
+        if let Some(segment) = ty_path.path.segments.last_mut() {
+            if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+                if let Some(GenericArgument::Type(element_ty)) = args.args.last_mut() {
+                    return element_ty;
+                }
+            }
+        }
+    }
+    unreachable!("Expected non-plain field shape to have an element type")
+}
+
+/// Returns the item of the linked source code by its path, relative to the
+/// linked source module, e.g. `["req", "Payload"]`.
+pub(crate) fn find_item<'a, S: AsRef<str>>(
+    items: &'a [Item],
+    ident_path: &[S],
+) -> Option<&'a Item> {
+    let (item_ident, mod_path) = ident_path.split_last()?;
+    let mut items = items;
+    for mod_ident in mod_path {
+        items = items.iter().find_map(|item| match item {
+            Item::Mod(item_mod) if item_mod.ident == mod_ident.as_ref() => {
+                item_mod.content.as_ref().map(|(_, items)| items.as_slice())
+            }
+            _ => None,
+        })?;
+    }
+
+    items.iter().find(|item| match item {
+        Item::Struct(item_struct) => item_struct.ident == item_ident.as_ref(),
+        Item::Enum(item_enum) => item_enum.ident == item_ident.as_ref(),
+        _ => false,
+    })
+}
+
+/// Returns the oneof enum path, relative to the linked source module, and the
+/// enum item for the original struct field `Option<Oneof>`; returns `None` if
+/// the field is not a oneof field.
+pub(crate) fn oneof_enum<'a>(
+    config: &'a Config,
+    ty: &Type,
+    ident_stack: &[String],
+) -> Option<(Vec<String>, &'a ItemEnum)> {
+    // prost places the oneof enum into the module named after the struct,
+    // e.g. `Option<req::Payload>`
+    let (FieldShape::Option, Type::Path(enum_ty_path)) = field_shape(ty) else {
+        return None;
+    };
+    if enum_ty_path.qself.is_some()
+        || enum_ty_path.path.leading_colon.is_some()
+        || enum_ty_path.path.segments.first().unwrap().ident == "super"
+    {
+        return None;
+    }
+    let enum_stack: Vec<String> = ident_stack[..ident_stack.len() - 1]
+        .iter()
+        .cloned()
+        .chain(
+            enum_ty_path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string()),
+        )
+        .collect();
+    match find_item(&config.source.ast.items, &enum_stack)? {
+        Item::Enum(item_enum) => Some((enum_stack, item_enum)),
+        _ => None,
+    }
+}
+
+/// Returns the optional message fields of the struct, i.e. the fields required
+/// by the `*` wildcard; the oneof fields and the boxed recursive message fields
+/// are not included.
+pub(crate) fn message_fields<'a>(
+    config: &Config,
+    item_struct: &'a ItemStruct,
+    ident_stack: &[String],
+) -> Vec<&'a Ident> {
+    item_struct
+        .fields
+        .iter()
+        .filter(|field| match field_shape(&field.ty) {
+            (FieldShape::Option, Type::Path(element_ty_path)) => {
+                is_type_non_scalar(element_ty_path)
+                    && element_ty_path
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident != "Box")
+                    && oneof_enum(config, &field.ty, ident_stack).is_none()
+            }
+            _ => false,
+        })
+        .filter_map(|field| field.ident.as_ref())
+        .collect()
+}
+
+/// The oneof field narrowed to a single variant, see `variant(Variant)` field
+/// modifier.
+pub(crate) struct OneofVariant {
+    /// The oneof enum path, relative to the linked source module
+    pub enum_stack: Vec<String>,
+    /// The variant inner type in the mirrored enum
+    pub ty: Type,
+}
+
+/// Returns the oneof variant of the original struct field `Option<Oneof>`;
+/// aborts if the field is not a oneof field or the variant is not found.
+pub(crate) fn oneof_variant(
+    config: &Config,
+    ty: &Type,
+    ident_stack: &[String],
+    variant: &syn::Ident,
+) -> OneofVariant {
+    let not_oneof = || -> ! {
+        abort!(
+            variant,
+            format!(
+                "Field has type `{}`, which is not a oneof type",
+                quote!(#ty)
+            )
+        )
+    };
+
+    let Some((enum_stack, item_enum)) = oneof_enum(config, ty, ident_stack) else {
+        not_oneof();
+    };
+
+    let Some(mut variant_ty) = item_enum
+        .variants
+        .iter()
+        .find(|item_variant| item_variant.ident == *variant)
+        .and_then(|item_variant| item_variant.fields.iter().next())
+        .map(|field| field.ty.clone())
+    else {
+        abort!(
+            variant,
+            format!(
+                "Variant `{}` missing from oneof definition: {}",
+                variant,
+                enum_stack.join("::")
+            )
+        );
+    };
+
+    // the variant type is mirrored the same way the oneof enum does
+    let variant_name = variant.to_string();
+    if let Some(mapping) = type_mapping(config, &variant_ty, &enum_stack, &variant_name) {
+        variant_ty = mapping.ty;
+    }
+    resolve_type_paths(config, &mut variant_ty, &enum_stack, &variant_name);
+
+    OneofVariant {
+        enum_stack,
+        ty: variant_ty,
+    }
+}
+
+/// The element type, that is converted with the generated functions instead
+/// of the `TryFrom` and `Into` traits, e.g. `prost_types::Timestamp`.
+pub(crate) struct TypeMapping {
+    /// The element type in the mirrored item
+    pub ty: Type,
+    /// `fn(Original) -> Result<Mirrored, Error>`, a function path or a closure
+    pub try_from_fn: Expr,
+    /// `fn(Mirrored) -> Original`, a function path or a closure
+    pub into_fn: Expr,
+}
+
+/// Returns the mapping for the element type of the original item field; the
+/// `ident_stack` is the path of the item owning the field. The mappings are
+/// picked in the order of precedence:
+/// - the field type, specified with `with_field_type`;
+/// - the newtype, specified with `with_newtype`;
+/// - the type mapping, specified with `with_type_mapping`;
+/// - the wrapper message, specified with `with_transparent`;
+/// - the well-known types mappings.
+pub(crate) fn type_mapping(
+    config: &Config,
+    ty: &Type,
+    ident_stack: &[String],
+    field_name: &str,
+) -> Option<TypeMapping> {
+    if let Some(field_type) = config.field_type_spec(ident_stack, field_name) {
+        return Some(custom_type_mapping(&field_type.custom_type));
+    }
+    if let Some(mapping) = item_newtype::type_mapping(config, ident_stack, field_name) {
+        return Some(mapping);
+    }
+
+    type_mapping_spec(config, ty, ident_stack)
+        .map(|type_mapping| custom_type_mapping(&type_mapping.custom_type))
+        .or_else(|| item_transparent::type_mapping(config, ty, ident_stack))
+        .or_else(|| well_known_types::type_mapping(config, ty))
+        .or_else(|| well_known_types::wrapper_type_mapping(config, ty, ident_stack))
+}
+
+/// Returns the `with_type_mapping` spec for the original element type.
+fn type_mapping_spec<'a>(
+    config: &'a Config,
+    ty: &Type,
+    ident_stack: &[String],
+) -> Option<&'a TypeMappingSpec> {
+    let Type::Path(ty_path) = ty else {
+        return None;
+    };
+    if ty_path.qself.is_some() {
+        return None;
+    }
+
+    let path = &ty_path.path;
+    if path.leading_colon.is_some() || path.segments.first().unwrap().ident == "crate" {
+        let ident_path: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        config.type_mapping_spec(&ident_path, true)
+    } else {
+        config.type_mapping_spec(&relative_item_path(config, ty_path, ident_stack)?, false)
+    }
+}
+
+const CUSTOM_INTO_FN: &str = "|value: {ty}| {into_callee}(&value)";
+
+fn custom_type_mapping(custom_type: &CustomType) -> TypeMapping {
+    let into_fn = strfmt!(
+        CUSTOM_INTO_FN,
+        ty => {
+            let ty = &custom_type.ty;
+            quote!(#ty).to_string()
+        },
+        into_callee => callee_string(&custom_type.into_fn)
+    )
+    .unwrap();
+
+    TypeMapping {
+        ty: custom_type.ty.clone(),
+        try_from_fn: custom_type.try_from_fn.clone(),
+        into_fn: syn::parse_str(&into_fn).expect("Expected custom conversion closure"),
+    }
+}
+
+/// Returns the conversion function as a string, ready to be called; closures
+/// are parenthesized.
+fn callee_string(expr: &Expr) -> String {
+    match expr {
+        Expr::Closure(_) => format!("({})", quote!(#expr)),
+        _ => quote!(#expr).to_string(),
+    }
+}
+
+const MAPPED_TRY_FROM_PLAIN: &str = "{try_from_callee}({value})";
+const MAPPED_TRY_FROM_OPTION: &str = "{value}.map({try_from_fn}).transpose()";
+const MAPPED_TRY_FROM_VEC: &str =
+    "{value}.into_iter().map({try_from_fn}).collect::<Result<Vec<_>, _>>()";
+const MAPPED_TRY_FROM_HASHMAP: &str = r#"
+    {value}
+        .into_iter()
+        .map(|(key, value)| {try_from_callee}(value).map(|value| (key, value)))
+        .collect::<Result<std::collections::HashMap<_, _>, _>>()
+"#;
+
+/// Returns the expression converting the `value` of mapped element type
+/// within the field shape; the expression evaluates into `Result<_, Error>`.
+pub(crate) fn mapped_try_from_expr(
+    shape: FieldShape,
+    value: &str,
+    mapping: &TypeMapping,
+) -> String {
+    let template = match shape {
+        FieldShape::Plain => MAPPED_TRY_FROM_PLAIN,
+        FieldShape::Option => MAPPED_TRY_FROM_OPTION,
+        FieldShape::Vec => MAPPED_TRY_FROM_VEC,
+        FieldShape::HashMap => MAPPED_TRY_FROM_HASHMAP,
+    };
+    let try_from_fn = &mapping.try_from_fn;
+    strfmt!(
+        template,
+        value => value.to_string(),
+        try_from_fn => quote!(#try_from_fn).to_string(),
+        try_from_callee => callee_string(try_from_fn)
+    )
+    .unwrap()
+}
+
+const MAPPED_INTO_PLAIN: &str = "{into_callee}({value})";
+const MAPPED_INTO_OPTION: &str = "{value}.map({into_fn})";
+const MAPPED_INTO_VEC: &str = "{value}.into_iter().map({into_fn}).collect()";
+const MAPPED_INTO_HASHMAP: &str =
+    "{value}.into_iter().map(|(key, value)| (key, {into_callee}(value))).collect()";
+
+/// Returns the expression converting the `value` of mapped element type
+/// within the field shape back into the original type.
+pub(crate) fn mapped_into_expr(shape: FieldShape, value: &str, mapping: &TypeMapping) -> String {
+    let template = match shape {
+        FieldShape::Plain => MAPPED_INTO_PLAIN,
+        FieldShape::Option => MAPPED_INTO_OPTION,
+        FieldShape::Vec => MAPPED_INTO_VEC,
+        FieldShape::HashMap => MAPPED_INTO_HASHMAP,
+    };
+    let into_fn = &mapping.into_fn;
+    strfmt!(
+        template,
+        value => value.to_string(),
+        into_fn => quote!(#into_fn).to_string(),
+        into_callee => callee_string(into_fn)
+    )
+    .unwrap()
+}
+
 pub(crate) fn maybe_unwrap_option_type(ty: &Type) -> &Type {
     if let Type::Path(ty_path) = ty {
         if let Some(last_segment) = ty_path.path.segments.last() {
@@ -142,18 +704,22 @@ pub(crate) fn maybe_unwrap_option_type(ty: &Type) -> &Type {
 
 pub(crate) fn is_std_option_type(ty: &Type) -> bool {
     if let Type::Path(ty_path) = ty {
-        return ty_path.path.segments.last().map_or(false, |segment| {
-            segment.ident == "Option" && !segment.arguments.is_empty()
-        });
+        return ty_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option" && !segment.arguments.is_empty());
     }
     false
 }
 
 pub(crate) fn is_std_vec_type(ty: &Type) -> bool {
     if let Type::Path(ty_path) = ty {
-        return ty_path.path.segments.last().map_or(false, |segment| {
-            segment.ident == "Vec" && !segment.arguments.is_empty()
-        });
+        return ty_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Vec" && !segment.arguments.is_empty());
     }
     false
 }
@@ -170,7 +736,7 @@ const SCALAR_TYPES: [&str; 16] = [
 
 pub(crate) fn is_std_vec_non_scalar(ty: &Type) -> bool {
     if let Type::Path(ty_path) = ty {
-        return ty_path.path.segments.last().map_or(false, |segment| {
+        return ty_path.path.segments.last().is_some_and(|segment| {
             if segment.ident == "Vec" && !segment.arguments.is_empty() {
                 if let PathArguments::AngleBracketed(ref args) = segment.arguments {
                     if let Some(GenericArgument::Type(Type::Path(ref ty_path))) = args.args.first()
@@ -187,7 +753,7 @@ pub(crate) fn is_std_vec_non_scalar(ty: &Type) -> bool {
 
 pub(crate) fn is_std_hashmap_non_scalar(ty: &Type) -> bool {
     if let Type::Path(ty_path) = ty {
-        return ty_path.path.segments.last().map_or(false, |segment| {
+        return ty_path.path.segments.last().is_some_and(|segment| {
             if segment.ident == "HashMap" && !segment.arguments.is_empty() {
                 if let PathArguments::AngleBracketed(ref args) = segment.arguments {
                     if let Some(GenericArgument::Type(Type::Path(ref ty_path))) = args.args.last() {
@@ -216,41 +782,18 @@ pub(crate) fn is_type_non_scalar(ty_path: &TypePath) -> bool {
 
 pub(crate) mod items {
     use syn::Item;
-    use syn::ItemImpl;
-    use syn::ItemStruct;
+    use syn::ItemUse;
 
     pub const ERROR_STRUCT_NAME: &str = "Error";
     pub(crate) fn item_error() -> Vec<Item> {
-        const DEF_BLOCK: &str = r#"
-            #[derive(Debug)]
-            pub struct Error {
-                pub reason: &'static str,
-            }
-        "#;
-        let item_def_block: ItemStruct =
-            syn::parse_str(DEF_BLOCK).expect("Expected Error definition");
-
-        const IMPL_BLOCK_DISPLAY: &str = r#"
-            impl std::fmt::Display for Error {
-                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                    write!(f, "{}", self.reason)
-                }
-            }
-        "#;
-        let item_impl_block_display: ItemImpl =
-            syn::parse_str(IMPL_BLOCK_DISPLAY).expect("Expected impl Display for Error");
-
-        const IMPL_BLOCK_STD_ERROR: &str = r#"
-            impl std::error::Error for Error {}
+        // the error type is shared by all `include!` modules, so the nested
+        // errors keep the whole field path across the module boundaries
+        const USE_BLOCK: &str = r#"
+            pub use ::prost_unwrap::Error;
         "#;
-        let item_impl_block_error: ItemImpl = syn::parse_str(IMPL_BLOCK_STD_ERROR)
-            .expect("Expected impl std::error::Error for Error");
+        let item_use_block: ItemUse = syn::parse_str(USE_BLOCK).expect("Expected Error re-export");
 
-        vec![
-            Item::Struct(item_def_block),
-            Item::Impl(item_impl_block_display),
-            Item::Impl(item_impl_block_error),
-        ]
+        vec![Item::Use(item_use_block)]
     }
 
     pub const FUNCTION_NAME_CONVERT_OPTION_TRY_FROM: &str = "convert_option_try_from";
@@ -368,4 +911,19 @@ pub(crate) mod items {
             syn::parse_str(DEF_BLOCK).expect("Expected item_convert_hashmap_into definition");
         vec![item_def_block]
     }
+
+    pub const FUNCTION_NAME_IS_DEFAULT: &str = "is_default";
+    pub(crate) fn item_is_default() -> Vec<Item> {
+        const DEF_BLOCK: &str = r#"
+            pub fn is_default<T>(value: &T) -> bool
+            where
+                T: Default + PartialEq,
+            {
+                *value == T::default()
+            }
+        "#;
+        let item_def_block: Item =
+            syn::parse_str(DEF_BLOCK).expect("Expected item_is_default definition");
+        vec![item_def_block]
+    }
 }
@@ -0,0 +1,495 @@
+use proc_macro2::Span;
+use quote::quote;
+use strfmt::strfmt;
+use syn::Expr;
+use syn::GenericArgument;
+use syn::Ident;
+use syn::Item;
+use syn::Path;
+use syn::PathArguments;
+use syn::PathSegment;
+use syn::Type;
+
+use super::TypeMapping;
+use crate::include::Config;
+use crate::include::TimeMapping;
+
+pub const FUNCTION_NAME_CONVERT_TIMESTAMP_TRY_FROM: &str = "convert_timestamp_try_from";
+pub const FUNCTION_NAME_CONVERT_TIMESTAMP_INTO: &str = "convert_timestamp_into";
+pub const FUNCTION_NAME_CONVERT_DURATION_TRY_FROM: &str = "convert_duration_try_from";
+pub const FUNCTION_NAME_CONVERT_DURATION_INTO: &str = "convert_duration_into";
+pub const FUNCTION_NAME_CONVERT_STRUCT_TRY_FROM: &str = "convert_struct_try_from";
+pub const FUNCTION_NAME_CONVERT_STRUCT_INTO: &str = "convert_struct_into";
+pub const FUNCTION_NAME_CONVERT_VALUE_TRY_FROM: &str = "convert_value_try_from";
+pub const FUNCTION_NAME_CONVERT_VALUE_INTO: &str = "convert_value_into";
+pub const FUNCTION_NAME_CONVERT_LIST_VALUE_TRY_FROM: &str = "convert_list_value_try_from";
+pub const FUNCTION_NAME_CONVERT_LIST_VALUE_INTO: &str = "convert_list_value_into";
+
+/// Returns the mapping for `prost_types` well-known type, if the mapping for
+/// this type is configured; the `Struct`, `Value` and `ListValue` types are
+/// mapped into `serde_json` types if `with_json(serde_json)` is specified.
+pub(crate) fn type_mapping(config: &Config, ty: &Type) -> Option<TypeMapping> {
+    let (mapping, try_from_fn, into_fn) = match prost_types_ident(ty)?.as_str() {
+        "Timestamp" => (
+            timestamp_type(config.timestamp_mapping?),
+            FUNCTION_NAME_CONVERT_TIMESTAMP_TRY_FROM,
+            FUNCTION_NAME_CONVERT_TIMESTAMP_INTO,
+        ),
+        "Duration" => (
+            duration_type(config.duration_mapping?),
+            FUNCTION_NAME_CONVERT_DURATION_TRY_FROM,
+            FUNCTION_NAME_CONVERT_DURATION_INTO,
+        ),
+        "Struct" if config.json_mapping.is_some() => (
+            "::serde_json::Map<String, ::serde_json::Value>",
+            FUNCTION_NAME_CONVERT_STRUCT_TRY_FROM,
+            FUNCTION_NAME_CONVERT_STRUCT_INTO,
+        ),
+        "Value" if config.json_mapping.is_some() => (
+            "::serde_json::Value",
+            FUNCTION_NAME_CONVERT_VALUE_TRY_FROM,
+            FUNCTION_NAME_CONVERT_VALUE_INTO,
+        ),
+        "ListValue" if config.json_mapping.is_some() => (
+            "Vec<::serde_json::Value>",
+            FUNCTION_NAME_CONVERT_LIST_VALUE_TRY_FROM,
+            FUNCTION_NAME_CONVERT_LIST_VALUE_INTO,
+        ),
+        _ => return None,
+    };
+
+    Some(TypeMapping {
+        ty: syn::parse_str(mapping).expect("Expected mapped type to be valid"),
+        try_from_fn: path_expr(config.this_item_typepath([try_from_fn.to_string()])),
+        into_fn: path_expr(config.this_item_typepath([into_fn.to_string()])),
+    })
+}
+
+fn path_expr(path: Path) -> Expr {
+    Expr::Path(syn::ExprPath {
+        attrs: Vec::new(),
+        qself: None,
+        path,
+    })
+}
+
+/// The `google.protobuf` wrapper messages and their `value` field types.
+const WRAPPER_TYPES: [(&str, &str); 9] = [
+    ("DoubleValue", "f64"),
+    ("FloatValue", "f32"),
+    ("Int64Value", "i64"),
+    ("UInt64Value", "u64"),
+    ("Int32Value", "i32"),
+    ("UInt32Value", "u32"),
+    ("BoolValue", "bool"),
+    ("StringValue", "::prost::alloc::string::String"),
+    ("BytesValue", "::prost::alloc::vec::Vec<u8>"),
+];
+
+/// The crates providing `google.protobuf` wrapper messages at their root.
+const WRAPPER_CRATES: [&str; 2] = ["prost_types", "pbjson_types"];
+
+const WRAPPER_TRY_FROM_FN: &str =
+    "|value: {wrapper_typepath}| Ok::<_, {error_typepath}>(value.value)";
+const WRAPPER_INTO_FN: &str = "|value| {wrapper_typepath} {{ value }}";
+
+/// Returns the mapping for `google.protobuf` wrapper message into its value
+/// type, e.g. `StringValue` into `String`.
+///
+/// By default prost maps the wrapper fields into the values itself; the
+/// wrapper messages are present in the linked source code only if they are
+/// compiled locally (`super::google::protobuf::StringValue`), or are provided
+/// with prost `extern_path` (the absolute path, e.g.
+/// `::pbjson_types::StringValue`). The user messages named like wrappers,
+/// e.g. `outer::Int64Value`, are mirrored as usual.
+pub(crate) fn wrapper_type_mapping(
+    config: &Config,
+    ty: &Type,
+    ident_stack: &[String],
+) -> Option<TypeMapping> {
+    let Type::Path(ty_path) = ty else {
+        return None;
+    };
+    let segments = &ty_path.path.segments;
+    if ty_path.qself.is_some() || segments.len() < 2 {
+        return None;
+    }
+
+    let wrapper_ident = segments.last().unwrap().ident.to_string();
+    let (_, value_type) = WRAPPER_TYPES
+        .iter()
+        .find(|(wrapper, _)| *wrapper == wrapper_ident)?;
+
+    let wrapper_typepath = if ty_path.path.leading_colon.is_none() && segments[0].ident != "crate" {
+        // locally compiled wrappers, the path is relative to the field owner
+        let ident_path = super::relative_item_path(config, ty_path, ident_stack)?;
+        if !is_wrapper_module(&ident_path[..ident_path.len() - 1]) {
+            return None;
+        }
+        let mut path = config.orig_mod_path.clone();
+        path.segments
+            .extend(ident_path.iter().map(|ident| PathSegment {
+                ident: Ident::new(ident, Span::call_site()),
+                arguments: PathArguments::None,
+            }));
+        path
+    } else {
+        // extern wrappers, the user message of the same name is not mapped
+        let module: Vec<String> = segments
+            .iter()
+            .take(segments.len() - 1)
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if !matches!(module.as_slice(), [krate] if WRAPPER_CRATES.contains(&krate.as_str()))
+            && !is_wrapper_module(&module)
+        {
+            return None;
+        }
+        ty_path.path.clone()
+    };
+
+    let error_typepath = config.this_item_typepath([super::items::ERROR_STRUCT_NAME.to_string()]);
+    let try_from_fn = strfmt!(
+        WRAPPER_TRY_FROM_FN,
+        wrapper_typepath => quote!(#wrapper_typepath).to_string(),
+        error_typepath => quote!(#error_typepath).to_string()
+    )
+    .unwrap();
+    let into_fn = strfmt!(
+        WRAPPER_INTO_FN,
+        wrapper_typepath => quote!(#wrapper_typepath).to_string()
+    )
+    .unwrap();
+
+    Some(TypeMapping {
+        ty: syn::parse_str(value_type).expect("Expected wrapper value type to be valid"),
+        try_from_fn: syn::parse_str(&try_from_fn).expect("Expected wrapper conversion closure"),
+        into_fn: syn::parse_str(&into_fn).expect("Expected wrapper conversion closure"),
+    })
+}
+
+/// Returns `true` for the `google.protobuf` package module path.
+fn is_wrapper_module(module: &[String]) -> bool {
+    module.ends_with(&["google".to_string(), "protobuf".to_string()])
+}
+
+/// Returns the ident of `prost_types` item, e.g. `Timestamp` for
+/// `::prost_types::Timestamp`.
+pub(crate) fn prost_types_ident(ty: &Type) -> Option<String> {
+    if let Type::Path(ty_path) = ty {
+        let segments = &ty_path.path.segments;
+        if ty_path.qself.is_none() && segments.len() == 2 && segments[0].ident == "prost_types" {
+            return Some(segments[1].ident.to_string());
+        }
+    }
+    None
+}
+
+fn timestamp_type(mapping: TimeMapping) -> &'static str {
+    match mapping {
+        TimeMapping::Std => "std::time::SystemTime",
+        TimeMapping::Chrono => "::chrono::DateTime<::chrono::Utc>",
+        TimeMapping::Time => "::time::OffsetDateTime",
+    }
+}
+
+fn duration_type(mapping: TimeMapping) -> &'static str {
+    match mapping {
+        TimeMapping::Std => "std::time::Duration",
+        TimeMapping::Chrono => "::chrono::TimeDelta",
+        TimeMapping::Time => "::time::Duration",
+    }
+}
+
+/// Returns the conversion functions for the configured mappings.
+pub(crate) fn items(config: &Config) -> Vec<Item> {
+    let mut items = Vec::new();
+    if let Some(mapping) = config.timestamp_mapping {
+        items.extend(item_convert_timestamp(mapping));
+    }
+    if let Some(mapping) = config.duration_mapping {
+        items.extend(item_convert_duration(mapping));
+    }
+    if config.json_mapping.is_some() && has_json_fields(&config.source.ast.items) {
+        items.extend(item_convert_json());
+    }
+    items
+}
+
+/// Returns `true` if any struct or enum of the linked source code has the
+/// `Struct`, `Value` or `ListValue` field, possibly wrapped into `Option`,
+/// `Vec` or map.
+fn has_json_fields(items: &[Item]) -> bool {
+    fn is_json_type(ty: &Type) -> bool {
+        if matches!(
+            prost_types_ident(ty).as_deref(),
+            Some("Struct" | "Value" | "ListValue")
+        ) {
+            return true;
+        }
+        let Type::Path(ty_path) = ty else {
+            return false;
+        };
+        ty_path.path.segments.iter().any(|segment| {
+            let PathArguments::AngleBracketed(ref args) = segment.arguments else {
+                return false;
+            };
+            args.args.iter().any(|arg| match arg {
+                GenericArgument::Type(ty) => is_json_type(ty),
+                _ => false,
+            })
+        })
+    }
+
+    items.iter().any(|item| match item {
+        Item::Struct(item_struct) => item_struct
+            .fields
+            .iter()
+            .any(|field| is_json_type(&field.ty)),
+        Item::Enum(item_enum) => item_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .any(|field| is_json_type(&field.ty)),
+        Item::Mod(item_mod) => item_mod
+            .content
+            .as_ref()
+            .is_some_and(|(_, items)| has_json_fields(items)),
+        _ => false,
+    })
+}
+
+fn item_convert_timestamp(mapping: TimeMapping) -> Vec<Item> {
+    const DEF_BLOCK_STD: &str = r#"
+        pub fn convert_timestamp_try_from(
+            value: ::prost_types::Timestamp,
+        ) -> Result<std::time::SystemTime, Error> {
+            if !(0..1_000_000_000).contains(&value.nanos) {
+                return Err(Error::new("timestamp nanos are out of range"));
+            }
+            std::time::SystemTime::try_from(value).map_err(Error::custom)
+        }
+
+        pub fn convert_timestamp_into(value: std::time::SystemTime) -> ::prost_types::Timestamp {
+            value.into()
+        }
+    "#;
+
+    const DEF_BLOCK_CHRONO: &str = r#"
+        pub fn convert_timestamp_try_from(
+            value: ::prost_types::Timestamp,
+        ) -> Result<::chrono::DateTime<::chrono::Utc>, Error> {
+            if !(0..1_000_000_000).contains(&value.nanos) {
+                return Err(Error::new("timestamp nanos are out of range"));
+            }
+            ::chrono::DateTime::from_timestamp(value.seconds, value.nanos as u32)
+                .ok_or_else(|| Error::new("timestamp is out of range"))
+        }
+
+        pub fn convert_timestamp_into(
+            value: ::chrono::DateTime<::chrono::Utc>,
+        ) -> ::prost_types::Timestamp {
+            ::prost_types::Timestamp {
+                seconds: value.timestamp(),
+                // leap seconds are represented with nanos exceeding a second
+                nanos: value.timestamp_subsec_nanos().min(999_999_999) as i32,
+            }
+        }
+    "#;
+
+    const DEF_BLOCK_TIME: &str = r#"
+        pub fn convert_timestamp_try_from(
+            value: ::prost_types::Timestamp,
+        ) -> Result<::time::OffsetDateTime, Error> {
+            if !(0..1_000_000_000).contains(&value.nanos) {
+                return Err(Error::new("timestamp nanos are out of range"));
+            }
+            ::time::OffsetDateTime::from_unix_timestamp_nanos(
+                i128::from(value.seconds) * 1_000_000_000 + i128::from(value.nanos),
+            )
+            .map_err(Error::custom)
+        }
+
+        pub fn convert_timestamp_into(value: ::time::OffsetDateTime) -> ::prost_types::Timestamp {
+            ::prost_types::Timestamp {
+                seconds: value.unix_timestamp(),
+                nanos: value.nanosecond() as i32,
+            }
+        }
+    "#;
+
+    let def_block = match mapping {
+        TimeMapping::Std => DEF_BLOCK_STD,
+        TimeMapping::Chrono => DEF_BLOCK_CHRONO,
+        TimeMapping::Time => DEF_BLOCK_TIME,
+    };
+    let file: syn::File =
+        syn::parse_str(def_block).expect("Expected timestamp conversion functions definition");
+    file.items
+}
+
+fn item_convert_duration(mapping: TimeMapping) -> Vec<Item> {
+    const DEF_BLOCK_STD: &str = r#"
+        pub fn convert_duration_try_from(
+            value: ::prost_types::Duration,
+        ) -> Result<std::time::Duration, Error> {
+            if value.nanos <= -1_000_000_000
+                || value.nanos >= 1_000_000_000
+                || (value.seconds > 0 && value.nanos < 0)
+                || (value.seconds < 0 && value.nanos > 0)
+            {
+                return Err(Error::new("duration nanos are out of range"));
+            }
+            std::time::Duration::try_from(value).map_err(Error::custom)
+        }
+
+        // durations exceeding the protobuf range are saturated
+        pub fn convert_duration_into(value: std::time::Duration) -> ::prost_types::Duration {
+            ::prost_types::Duration::try_from(value).unwrap_or(::prost_types::Duration {
+                seconds: i64::MAX,
+                nanos: 999_999_999,
+            })
+        }
+    "#;
+
+    const DEF_BLOCK_CHRONO: &str = r#"
+        pub fn convert_duration_try_from(
+            value: ::prost_types::Duration,
+        ) -> Result<::chrono::TimeDelta, Error> {
+            if value.nanos <= -1_000_000_000
+                || value.nanos >= 1_000_000_000
+                || (value.seconds > 0 && value.nanos < 0)
+                || (value.seconds < 0 && value.nanos > 0)
+            {
+                return Err(Error::new("duration nanos are out of range"));
+            }
+            ::chrono::TimeDelta::try_seconds(value.seconds)
+                .and_then(|seconds| {
+                    seconds.checked_add(&::chrono::TimeDelta::nanoseconds(value.nanos.into()))
+                })
+                .ok_or_else(|| Error::new("duration is out of range"))
+        }
+
+        pub fn convert_duration_into(value: ::chrono::TimeDelta) -> ::prost_types::Duration {
+            ::prost_types::Duration {
+                seconds: value.num_seconds(),
+                nanos: value.subsec_nanos(),
+            }
+        }
+    "#;
+
+    const DEF_BLOCK_TIME: &str = r#"
+        pub fn convert_duration_try_from(
+            value: ::prost_types::Duration,
+        ) -> Result<::time::Duration, Error> {
+            if value.nanos <= -1_000_000_000
+                || value.nanos >= 1_000_000_000
+                || (value.seconds > 0 && value.nanos < 0)
+                || (value.seconds < 0 && value.nanos > 0)
+            {
+                return Err(Error::new("duration nanos are out of range"));
+            }
+            ::time::Duration::seconds(value.seconds)
+                .checked_add(::time::Duration::nanoseconds(value.nanos.into()))
+                .ok_or_else(|| Error::new("duration is out of range"))
+        }
+
+        pub fn convert_duration_into(value: ::time::Duration) -> ::prost_types::Duration {
+            ::prost_types::Duration {
+                seconds: value.whole_seconds(),
+                nanos: value.subsec_nanoseconds(),
+            }
+        }
+    "#;
+
+    let def_block = match mapping {
+        TimeMapping::Std => DEF_BLOCK_STD,
+        TimeMapping::Chrono => DEF_BLOCK_CHRONO,
+        TimeMapping::Time => DEF_BLOCK_TIME,
+    };
+    let file: syn::File =
+        syn::parse_str(def_block).expect("Expected duration conversion functions definition");
+    file.items
+}
+
+fn item_convert_json() -> Vec<Item> {
+    const DEF_BLOCK: &str = r#"
+        pub fn convert_value_try_from(
+            value: ::prost_types::Value,
+        ) -> Result<::serde_json::Value, Error> {
+            use ::prost_types::value::Kind;
+
+            Ok(match value.kind {
+                None | Some(Kind::NullValue(_)) => ::serde_json::Value::Null,
+                Some(Kind::NumberValue(number)) => ::serde_json::Number::from_f64(number)
+                    .map(::serde_json::Value::Number)
+                    .ok_or_else(|| Error::new("number is not finite"))?,
+                Some(Kind::StringValue(string)) => ::serde_json::Value::String(string),
+                Some(Kind::BoolValue(bool_)) => ::serde_json::Value::Bool(bool_),
+                Some(Kind::StructValue(struct_)) => {
+                    ::serde_json::Value::Object(convert_struct_try_from(struct_)?)
+                }
+                Some(Kind::ListValue(list)) => {
+                    ::serde_json::Value::Array(convert_list_value_try_from(list)?)
+                }
+            })
+        }
+
+        pub fn convert_value_into(value: ::serde_json::Value) -> ::prost_types::Value {
+            use ::prost_types::value::Kind;
+
+            let kind = match value {
+                ::serde_json::Value::Null => {
+                    Kind::NullValue(::prost_types::NullValue::NullValue as i32)
+                }
+                ::serde_json::Value::Bool(bool_) => Kind::BoolValue(bool_),
+                // numbers are always representable as f64, unless serde_json
+                // `arbitrary_precision` feature is enabled
+                ::serde_json::Value::Number(number) => {
+                    Kind::NumberValue(number.as_f64().unwrap_or(f64::NAN))
+                }
+                ::serde_json::Value::String(string) => Kind::StringValue(string),
+                ::serde_json::Value::Array(list) => Kind::ListValue(convert_list_value_into(list)),
+                ::serde_json::Value::Object(struct_) => Kind::StructValue(convert_struct_into(struct_)),
+            };
+            ::prost_types::Value { kind: Some(kind) }
+        }
+
+        pub fn convert_struct_try_from(
+            value: ::prost_types::Struct,
+        ) -> Result<::serde_json::Map<String, ::serde_json::Value>, Error> {
+            value
+                .fields
+                .into_iter()
+                .map(|(key, value)| convert_value_try_from(value).map(|value| (key, value)))
+                .collect()
+        }
+
+        pub fn convert_struct_into(
+            value: ::serde_json::Map<String, ::serde_json::Value>,
+        ) -> ::prost_types::Struct {
+            ::prost_types::Struct {
+                fields: value
+                    .into_iter()
+                    .map(|(key, value)| (key, convert_value_into(value)))
+                    .collect(),
+            }
+        }
+
+        pub fn convert_list_value_try_from(
+            value: ::prost_types::ListValue,
+        ) -> Result<Vec<::serde_json::Value>, Error> {
+            value.values.into_iter().map(convert_value_try_from).collect()
+        }
+
+        pub fn convert_list_value_into(value: Vec<::serde_json::Value>) -> ::prost_types::ListValue {
+            ::prost_types::ListValue {
+                values: value.into_iter().map(convert_value_into).collect(),
+            }
+        }
+    "#;
+
+    let file: syn::File =
+        syn::parse_str(DEF_BLOCK).expect("Expected json conversion functions definition");
+    file.items
+}
@@ -0,0 +1,729 @@
+//! The `buf.validate` and `protoc-gen-validate` field rules, parsed from the
+//! `.proto` source code.
+//!
+//! prost discards the custom field options, so the rules are read from the
+//! `.proto` file itself: the parser skips everything but the messages, their
+//! fields and the field options.
+
+use heck::ToSnakeCase;
+use heck::ToUpperCamelCase;
+use proc_macro2::Span;
+
+/// The option names the field rules are specified with.
+const RULES_OPTIONS: [&str; 2] = ["buf.validate.field", "validate.rules"];
+
+/// The numeric rule kinds, e.g. `(buf.validate.field).int32.gt = 0`.
+const NUMERIC_KINDS: [&str; 12] = [
+    "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32", "fixed64", "sfixed32",
+    "sfixed64", "float", "double",
+];
+
+/// The validation rules of the `.proto` file fields.
+#[derive(Clone, Debug)]
+pub(crate) struct ProtoRules {
+    /// Span of the `.proto` file path literal
+    pub path_span: Span,
+    /// Proto package, e.g. `["root", "inner"]`
+    pub package: Vec<String>,
+    pub fields: Vec<FieldRules>,
+}
+
+/// The validation rules of the message field.
+#[derive(Clone, Debug)]
+pub(crate) struct FieldRules {
+    /// Struct path relative to the package module, e.g. `["order", "Line"]`
+    pub struct_path: Vec<String>,
+    /// Struct field name, e.g. `user_id`
+    pub field: String,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Rule {
+    /// The field must be set: `Option<T>` field is unwrapped, other fields
+    /// must not have the default value
+    Required,
+    /// Minimum string length in characters or bytes length
+    MinLen(u64),
+    /// Maximum string length in characters or bytes length
+    MaxLen(u64),
+    /// Exact string length in characters or bytes length
+    Len(u64),
+    Prefix(String),
+    Suffix(String),
+    Contains(String),
+    /// The number comparisons, the value is the number literal
+    Gt(String),
+    Gte(String),
+    Lt(String),
+    Lte(String),
+    Const(String),
+    /// Minimum number of repeated field items or map field pairs
+    MinItems(u64),
+    /// Maximum number of repeated field items or map field pairs
+    MaxItems(u64),
+}
+
+impl ProtoRules {
+    /// Parses the `.proto` file contents; returns the error message with the
+    /// line number on the unexpected syntax or unsupported rule.
+    pub fn parse(contents: &str, path_span: Span) -> Result<Self, String> {
+        let tokens = tokenize(contents)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            rules: ProtoRules {
+                path_span,
+                package: Vec::new(),
+                fields: Vec::new(),
+            },
+        };
+        parser.parse_file()?;
+        Ok(parser.rules)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// Identifier, possibly dotted, e.g. `google.protobuf.Timestamp`
+    Ident(String),
+    Str(String),
+    Number(String),
+    Punct(char),
+}
+
+/// Splits the `.proto` source code into tokens with their line numbers,
+/// dropping the comments.
+fn tokenize(contents: &str) -> Result<Vec<(Token, usize)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = contents.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => (),
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' | '\'' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(escaped) => value.push(escaped),
+                            None => return Err(format!("unterminated string (line {line})")),
+                        },
+                        Some(c) => value.push(c),
+                        None => return Err(format!("unterminated string (line {line})")),
+                    }
+                }
+                tokens.push((Token::Str(value), line));
+            }
+            c if c.is_ascii_digit() => {
+                let mut value = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_alphanumeric() && c != '.' {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Number(value), line));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '.' => {
+                let mut value = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_alphanumeric() && c != '_' && c != '.' {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Ident(value), line));
+            }
+            c => tokens.push((Token::Punct(c), line)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// The option value, either scalar or aggregate.
+#[derive(Clone, Debug)]
+enum Value {
+    Ident(String),
+    Str(String),
+    Number(String),
+    List,
+    Message(Vec<(String, Value)>),
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    rules: ProtoRules,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map(|(_, line)| *line)
+            .unwrap_or_default()
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} (line {})", message, self.line())
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .map(|(token, _)| token.clone())
+            .ok_or_else(|| self.error("unexpected end of file"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Token::Punct(c))
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<(), String> {
+        match self.next()? {
+            Token::Punct(p) if p == c => Ok(()),
+            _ => {
+                self.pos -= 1;
+                Err(self.error(&format!("expected `{c}`")))
+            }
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected identifier"))
+            }
+        }
+    }
+
+    /// Skips the tokens up to and including the `;` punct.
+    fn skip_statement(&mut self) -> Result<(), String> {
+        while self.next()? != Token::Punct(';') {}
+        Ok(())
+    }
+
+    /// Skips the tokens up to and including the balanced `{ ... }` block.
+    fn skip_block(&mut self) -> Result<(), String> {
+        while self.next()? != Token::Punct('{') {}
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Punct('{') => depth += 1,
+                Token::Punct('}') => depth -= 1,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_file(&mut self) -> Result<(), String> {
+        while let Some(token) = self.peek().cloned() {
+            match token {
+                Token::Ident(ident) if ident == "package" => {
+                    self.next()?;
+                    let package = self.expect_ident()?;
+                    self.rules.package = package.split('.').map(ToString::to_string).collect();
+                    self.expect_punct(';')?;
+                }
+                Token::Ident(ident) if ident == "message" => {
+                    self.next()?;
+                    self.parse_message(&[])?;
+                }
+                Token::Ident(ident) if ["enum", "service", "extend"].contains(&ident.as_str()) => {
+                    self.skip_block()?
+                }
+                Token::Ident(_) => self.skip_statement()?,
+                Token::Punct(';') => {
+                    self.next()?;
+                }
+                _ => return Err(self.error("unexpected token")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the message body; `mod_path` is the path of the parent messages
+    /// modules, e.g. `["order"]`.
+    fn parse_message(&mut self, mod_path: &[String]) -> Result<(), String> {
+        let name = self.expect_ident()?;
+        let struct_path: Vec<String> = mod_path
+            .iter()
+            .cloned()
+            .chain([name.to_upper_camel_case()])
+            .collect();
+        let nested_mod_path: Vec<String> = mod_path
+            .iter()
+            .cloned()
+            .chain([name.to_snake_case()])
+            .collect();
+        self.expect_punct('{')?;
+
+        while !self.is_punct('}') {
+            match self.peek().cloned() {
+                Some(Token::Ident(ident)) if ident == "message" => {
+                    self.next()?;
+                    self.parse_message(&nested_mod_path)?;
+                }
+                Some(Token::Ident(ident)) if ["enum", "extend"].contains(&ident.as_str()) => {
+                    self.skip_block()?
+                }
+                Some(Token::Ident(ident))
+                    if ["option", "reserved", "extensions"].contains(&ident.as_str()) =>
+                {
+                    self.skip_statement()?
+                }
+                Some(Token::Ident(ident)) if ident == "oneof" => {
+                    self.next()?;
+                    self.expect_ident()?;
+                    self.expect_punct('{')?;
+                    while !self.is_punct('}') {
+                        if self.peek() == Some(&Token::Ident("option".to_string())) {
+                            self.skip_statement()?;
+                            continue;
+                        }
+                        let line = self.line();
+                        let (field, rules) = self.parse_field()?;
+                        if !rules.is_empty() {
+                            return Err(format!(
+                                "validation rules of oneof field `{field}` are not supported (line {line})"
+                            ));
+                        }
+                    }
+                    self.expect_punct('}')?;
+                }
+                Some(Token::Punct(';')) => {
+                    self.next()?;
+                }
+                Some(_) => {
+                    let (field, rules) = self.parse_field()?;
+                    if !rules.is_empty() {
+                        self.rules.fields.push(FieldRules {
+                            struct_path: struct_path.clone(),
+                            field,
+                            rules,
+                        });
+                    }
+                }
+                None => return Err(self.error("unexpected end of file")),
+            }
+        }
+        self.expect_punct('}')?;
+
+        Ok(())
+    }
+
+    /// Parses the field definition, returns the struct field name and the
+    /// field rules.
+    fn parse_field(&mut self) -> Result<(String, Vec<Rule>), String> {
+        let ty = self.expect_ident()?;
+        if ty == "map" {
+            self.expect_punct('<')?;
+            while self.next()? != Token::Punct('>') {}
+        } else if ["repeated", "optional", "required"].contains(&ty.as_str()) {
+            self.expect_ident()?;
+        }
+        let field = self.expect_ident()?.to_snake_case();
+        self.expect_punct('=')?;
+        self.next()?;
+
+        let mut rules = Vec::new();
+        if self.is_punct('[') {
+            self.next()?;
+            loop {
+                let line = self.line();
+                let (name, value) = self.parse_option()?;
+                rules.extend(option_rules(&name, value).map_err(|e| format!("{e} (line {line})"))?);
+                if self.is_punct(',') {
+                    self.next()?;
+                    continue;
+                }
+                self.expect_punct(']')?;
+                break;
+            }
+        }
+        self.expect_punct(';')?;
+
+        Ok((field, rules))
+    }
+
+    /// Parses the field option, e.g. `(buf.validate.field).string.min_len = 1`;
+    /// the extension name in parens is kept as a single path segment.
+    fn parse_option(&mut self) -> Result<(Vec<String>, Value), String> {
+        let mut name = Vec::new();
+        while !self.is_punct('=') {
+            match self.next()? {
+                Token::Punct('(') => {
+                    let extension = self.expect_ident()?;
+                    name.push(extension.trim_start_matches('.').to_string());
+                    self.expect_punct(')')?;
+                }
+                Token::Ident(ident) => name.extend(
+                    ident
+                        .split('.')
+                        .filter(|segment| !segment.is_empty())
+                        .map(ToString::to_string),
+                ),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("unexpected token in option name"));
+                }
+            }
+        }
+        self.expect_punct('=')?;
+        let value = self.parse_value()?;
+        Ok((name, value))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(Value::Ident(ident)),
+            Token::Str(value) => {
+                // adjacent string literals are concatenated
+                let mut value = value;
+                while let Some(Token::Str(next)) = self.peek().cloned() {
+                    self.next()?;
+                    value.push_str(&next);
+                }
+                Ok(Value::Str(value))
+            }
+            Token::Number(number) => Ok(Value::Number(number)),
+            Token::Punct('-') => match self.next()? {
+                Token::Number(number) => Ok(Value::Number(format!("-{number}"))),
+                Token::Ident(ident) => Ok(Value::Number(format!("-{ident}"))),
+                _ => Err(self.error("expected number")),
+            },
+            Token::Punct('[') => {
+                while !self.is_punct(']') {
+                    self.parse_value()?;
+                    if self.is_punct(',') {
+                        self.next()?;
+                    }
+                }
+                self.expect_punct(']')?;
+                Ok(Value::List)
+            }
+            Token::Punct('{') => {
+                let mut entries = Vec::new();
+                while !self.is_punct('}') {
+                    let key = match self.next()? {
+                        Token::Ident(ident) => ident,
+                        Token::Punct('[') => {
+                            let extension = self.expect_ident()?;
+                            self.expect_punct(']')?;
+                            extension
+                        }
+                        _ => return Err(self.error("expected field name")),
+                    };
+                    if self.is_punct(':') {
+                        self.next()?;
+                    }
+                    entries.push((key, self.parse_value()?));
+                    if self.is_punct(',') || self.is_punct(';') {
+                        self.next()?;
+                    }
+                }
+                self.expect_punct('}')?;
+                Ok(Value::Message(entries))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error("unexpected option value"))
+            }
+        }
+    }
+}
+
+/// Returns the rules of the field option; the options other than the field
+/// rules are ignored.
+fn option_rules(name: &[String], value: Value) -> Result<Vec<Rule>, String> {
+    let Some((option, path)) = name.split_first() else {
+        return Ok(Vec::new());
+    };
+    if !RULES_OPTIONS.contains(&option.as_str()) {
+        return Ok(Vec::new());
+    }
+
+    let mut flat = Vec::new();
+    flatten(path.to_vec(), value, &mut flat);
+    flat.into_iter()
+        .filter_map(|(path, value)| rule(option, &path, value).transpose())
+        .collect()
+}
+
+/// Flattens the aggregate option value into the dotted paths.
+fn flatten(path: Vec<String>, value: Value, flat: &mut Vec<(Vec<String>, Value)>) {
+    match value {
+        Value::Message(entries) => {
+            for (key, value) in entries {
+                let mut path = path.clone();
+                path.push(key);
+                flatten(path, value, flat);
+            }
+        }
+        value => flat.push((path, value)),
+    }
+}
+
+fn rule(option: &str, path: &[String], value: Value) -> Result<Option<Rule>, String> {
+    let path: Vec<&str> = path.iter().map(String::as_str).collect();
+    let unsupported = || {
+        format!(
+            "unsupported validation rule `({}).{}`",
+            option,
+            path.join(".")
+        )
+    };
+    let length = |value: &Value| match value {
+        Value::Number(number) => number.parse::<u64>().map_err(|_| unsupported()),
+        _ => Err(unsupported()),
+    };
+    let string = |value: &Value| match value {
+        Value::Str(value) => Ok(value.clone()),
+        _ => Err(unsupported()),
+    };
+    let number = |value: &Value| match value {
+        Value::Number(number) if is_decimal_number(number) => Ok(number.clone()),
+        Value::Number(number) => Err(format!(
+            "unsupported number `{}` of validation rule `({}).{}`, must be a decimal literal",
+            number,
+            option,
+            path.join(".")
+        )),
+        _ => Err(unsupported()),
+    };
+
+    let rule = match path.as_slice() {
+        ["required"] | ["message", "required"] => match value {
+            Value::Ident(ident) if ident == "true" => Rule::Required,
+            Value::Ident(ident) if ident == "false" => return Ok(None),
+            _ => return Err(unsupported()),
+        },
+        ["string" | "bytes", "min_len"] => Rule::MinLen(length(&value)?),
+        ["string" | "bytes", "max_len"] => Rule::MaxLen(length(&value)?),
+        ["string" | "bytes", "len"] => Rule::Len(length(&value)?),
+        ["string", "prefix"] => Rule::Prefix(string(&value)?),
+        ["string", "suffix"] => Rule::Suffix(string(&value)?),
+        ["string", "contains"] => Rule::Contains(string(&value)?),
+        [kind, rule] if NUMERIC_KINDS.contains(kind) => match *rule {
+            "gt" => Rule::Gt(number(&value)?),
+            "gte" => Rule::Gte(number(&value)?),
+            "lt" => Rule::Lt(number(&value)?),
+            "lte" => Rule::Lte(number(&value)?),
+            "const" => Rule::Const(number(&value)?),
+            _ => return Err(unsupported()),
+        },
+        ["repeated", "min_items"] | ["map", "min_pairs"] => Rule::MinItems(length(&value)?),
+        ["repeated", "max_items"] | ["map", "max_pairs"] => Rule::MaxItems(length(&value)?),
+        _ => return Err(unsupported()),
+    };
+    Ok(Some(rule))
+}
+
+/// Returns `true` for the decimal integer or float literal, e.g. `-1`, `0.5`
+/// or `1e3`, which is pasted into the generated code as is; `inf`, `nan` and
+/// the hex and octal literals are not supported.
+fn is_decimal_number(number: &str) -> bool {
+    let digits = number.strip_prefix('-').unwrap_or(number);
+    let (mantissa, exponent) = match digits.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (digits, None),
+    };
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    let is_mantissa = match mantissa.split_once('.') {
+        Some((int, fract)) => is_digits(int) && (fract.is_empty() || is_digits(fract)),
+        None => is_digits(mantissa),
+    };
+    let is_octal = mantissa.len() > 1 && mantissa.starts_with('0') && !mantissa.contains('.');
+    let is_exponent = match exponent {
+        Some(exponent) => is_digits(exponent.strip_prefix('-').unwrap_or(exponent)),
+        None => true,
+    };
+    is_mantissa && !is_octal && is_exponent
+}
+
+impl FieldRules {
+    /// Returns `true` if the rules are of the struct field; the field name
+    /// escaped by prost as raw identifier, e.g. `r#type`, is unescaped.
+    pub fn is_field(&self, struct_path: &[String], field_name: &str) -> bool {
+        self.struct_path == struct_path
+            && self.field == field_name.strip_prefix("r#").unwrap_or(field_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rules() {
+        let rules = ProtoRules::parse(
+            r#"
+            syntax = "proto3";
+            package acme.shop;
+            import "buf/validate/validate.proto";
+
+            // comment with `message` keyword
+            message Order {
+              option (buf.validate.message).disabled = false;
+              message Line {
+                int64 amount = 1 [(buf.validate.field).int64.gt = 0];
+              }
+              /* the name */
+              string name = 1 [
+                (buf.validate.field).string = {min_len: 1, max_len: 10},
+                deprecated = true
+              ];
+              repeated Line lines = 2 [(validate.rules).repeated.min_items = 1];
+              Line primary = 3 [(buf.validate.field).required = true];
+              map<string, int32> tags = 4;
+              oneof kind {
+                string a = 5;
+              }
+            }
+            "#,
+            Span::call_site(),
+        )
+        .unwrap();
+
+        assert_eq!(rules.package, vec!["acme", "shop"]);
+        let fields: Vec<_> = rules
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.struct_path.join("::"),
+                    field.field.as_str(),
+                    &field.rules,
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (
+                    "order::Line".to_string(),
+                    "amount",
+                    &vec![Rule::Gt("0".to_string())]
+                ),
+                (
+                    "Order".to_string(),
+                    "name",
+                    &vec![Rule::MinLen(1), Rule::MaxLen(10)]
+                ),
+                ("Order".to_string(), "lines", &vec![Rule::MinItems(1)]),
+                ("Order".to_string(), "primary", &vec![Rule::Required]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_unsupported_rule() {
+        let error = ProtoRules::parse(
+            r#"
+            message Order {
+              string email = 1 [(buf.validate.field).string.email = true];
+            }
+            "#,
+            Span::call_site(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            "unsupported validation rule `(buf.validate.field).string.email` (line 3)"
+        );
+    }
+
+    #[test]
+    fn parse_unsupported_number() {
+        for (literal, number) in [
+            ("inf", None),
+            ("-inf", Some("-inf")),
+            ("nan", None),
+            ("0x10", Some("0x10")),
+            ("010", Some("010")),
+        ] {
+            let error = ProtoRules::parse(
+                &format!(
+                    "message Order {{ double total = 1 [(buf.validate.field).double.gt = {literal}]; }}"
+                ),
+                Span::call_site(),
+            )
+            .unwrap_err();
+
+            let expected = match number {
+                Some(number) => format!(
+                    "unsupported number `{number}` of validation rule `(buf.validate.field).double.gt`, must be a decimal literal (line 1)"
+                ),
+                None => "unsupported validation rule `(buf.validate.field).double.gt` (line 1)"
+                    .to_string(),
+            };
+            assert_eq!(error, expected);
+        }
+
+        let rules = ProtoRules::parse(
+            "message Order { double total = 1 [(buf.validate.field).double = {gt: 0, lt: 1.5e3}]; }",
+            Span::call_site(),
+        )
+        .unwrap();
+        assert_eq!(
+            rules.fields[0].rules,
+            vec![Rule::Gt("0".to_string()), Rule::Lt("1.5e3".to_string())]
+        );
+    }
+
+    #[test]
+    fn match_raw_field() {
+        let rules = ProtoRules::parse(
+            "message Order { string type = 1 [(buf.validate.field).string.min_len = 1]; }",
+            Span::call_site(),
+        )
+        .unwrap();
+
+        let struct_path = vec!["Order".to_string()];
+        assert!(rules.fields[0].is_field(&struct_path, "r#type"));
+        assert!(rules.fields[0].is_field(&struct_path, "type"));
+        assert!(!rules.fields[0].is_field(&struct_path, "r#typ"));
+    }
+}
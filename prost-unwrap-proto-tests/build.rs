@@ -30,9 +30,61 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     positive_test!("repeated_scalar");
     positive_test!("hashmap_of_structs");
     positive_test!("hashmap_of_scalars");
+    positive_test!("newtypes");
     positive_test!("oneof_scalars");
     positive_test!("oneof_structs");
     positive_test!("oneof_enums");
+    positive_test!("optional_fields");
+    positive_test!("cross_package");
+    positive_test!("default_values");
+    positive_test!("field_modifiers");
+    positive_test!("custom_types");
+    positive_test!("well_known_time");
+    positive_test!("well_known_json");
+    positive_test!("validate_rules");
+    positive_test!("validators");
+    positive_test!("oneof_variant");
+    positive_test!("oneof_message_enum");
+    positive_test!("transparent_wrappers");
+    positive_test!("named_views");
+    positive_test!("nested_field_paths");
+    positive_test!("nested_field_paths_base");
+    positive_test!("required_field_policies");
+    positive_test!("skipped_fields");
+    positive_test!("renamed_items");
+    positive_test!("flattened_fields");
+    positive_test!("private_fields");
+    positive_test!("typed_builders");
+
+    // the `prost::Name` implementations are required to pack and unpack
+    // `prost_types::Any` payloads
+    let src = PathBuf::from("tests/positive/any_payload");
+    prost_build::Config::new()
+        .out_dir(src.join(".proto_out"))
+        .enable_type_names()
+        .compile_protos(&[src.join(".proto/test.proto")], &[src.join(".proto")])?;
+
+    // the wrapper messages are compiled locally instead of being mapped into
+    // the values by prost
+    let src = PathBuf::from("tests/positive/wrapper_types");
+    prost_build::Config::new()
+        .out_dir(src.join(".proto_out"))
+        .compile_well_known_types()
+        .compile_protos(&[src.join(".proto/test.proto")], &[src.join(".proto")])?;
+
+    // the `common` package is compiled separately and is referenced with prost
+    // `extern_path`, as if it was provided by another crate
+    let src = PathBuf::from("tests/positive/extern_path");
+    prost_build::Config::new()
+        .out_dir(src.join(".proto_out"))
+        .compile_protos(&[src.join(".proto/common.proto")], &[src.join(".proto")])?;
+    prost_build::Config::new()
+        .out_dir(src.join(".proto_out"))
+        .extern_path(
+            ".common",
+            "crate::positive::extern_path::common_crate::generated::common",
+        )
+        .compile_protos(&[src.join(".proto/test.proto")], &[src.join(".proto")])?;
 
     Ok(())
 }
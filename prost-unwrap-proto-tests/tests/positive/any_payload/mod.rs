@@ -0,0 +1,115 @@
+use prost::Name;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/any_payload/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::any_payload::generated)
+        .with_this_mod(crate::positive::any_payload::sane)
+        .with_struct(OrderCreated, [item])
+        .with_struct(Envelope, [payload])
+        .with_any(Envelope.payload, [OrderCreated, OrderCancelled])
+        .with_any(Envelope.history, [OrderCreated, OrderCancelled]));
+    }
+}
+
+fn order_created() -> generated::test::OrderCreated {
+    generated::test::OrderCreated {
+        id: "1".to_string(),
+        item: Some(generated::test::Item {
+            sku: "a".to_string(),
+        }),
+    }
+}
+
+fn order_cancelled() -> generated::test::OrderCancelled {
+    generated::test::OrderCancelled {
+        id: "1".to_string(),
+        reason: "b".to_string(),
+    }
+}
+
+fn original() -> generated::test::Envelope {
+    generated::test::Envelope {
+        payload: Some(prost_types::Any::from_msg(&order_created()).unwrap()),
+        history: vec![prost_types::Any::from_msg(&order_cancelled()).unwrap()],
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Envelope = orig.clone().try_into().unwrap();
+    assert!(matches!(
+        sane.payload,
+        sane::test::EnvelopePayload::OrderCreated(ref message) if message.item.sku == "a"
+    ));
+    assert!(matches!(
+        sane.history[0],
+        sane::test::EnvelopeHistory::OrderCancelled(ref message) if message.reason == "b"
+    ));
+    assert_eq!(orig, Into::<generated::test::Envelope>::into(sane));
+}
+
+#[test]
+fn test_error_unknown_type_url() {
+    let orig = generated::test::Envelope {
+        payload: Some(prost_types::Any::from_msg(&generated::test::Item::default()).unwrap()),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Envelope>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "payload: unknown type url `{}`",
+            generated::test::Item::type_url()
+        )
+    );
+}
+
+#[test]
+fn test_error_decode() {
+    let orig = generated::test::Envelope {
+        history: vec![prost_types::Any {
+            type_url: generated::test::OrderCancelled::type_url(),
+            value: vec![0xff],
+        }],
+        ..original()
+    };
+    let error = TryInto::<sane::test::Envelope>::try_into(orig)
+        .err()
+        .unwrap();
+    assert!(error.to_string().starts_with("history.OrderCancelled: "));
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn test_error_nested() {
+    let orig = generated::test::Envelope {
+        payload: Some(
+            prost_types::Any::from_msg(&generated::test::OrderCreated {
+                item: None,
+                ..order_created()
+            })
+            .unwrap(),
+        ),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Envelope>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "payload.OrderCreated.item: field is required"
+    );
+}
@@ -0,0 +1,56 @@
+pub mod generated {
+    pub mod common {
+        include!(".proto_out/common.rs");
+    }
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+// mirrored separately, the module tree does not follow the original one
+pub mod sane_common {
+    pub mod common {
+        prost_unwrap::include!(from_source(
+            common,
+            "prost-unwrap-proto-tests/tests/positive/cross_package/.proto_out/common.rs"
+        )
+        .with_original_mod(crate::positive::cross_package::generated)
+        .with_this_mod(crate::positive::cross_package::sane_common)
+        .with_struct(MsgB, [f1]));
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/cross_package/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::cross_package::generated)
+        .with_this_mod(crate::positive::cross_package::sane)
+        .with_foreign_mod(common, crate::positive::cross_package::sane_common::common)
+        .with_struct(MsgC, [f1]));
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::MsgC {
+        f1: Some(generated::common::MsgB {
+            f1: Some(generated::common::MsgA { f1: 1 }),
+        }),
+    };
+    let sane: sane::test::MsgC = orig.clone().try_into().unwrap();
+    assert_eq!(orig, Into::<generated::test::MsgC>::into(sane));
+}
+
+#[test]
+fn test_error() {
+    let orig = generated::test::MsgC {
+        f1: Some(generated::common::MsgB { f1: None }),
+    };
+    let error = TryInto::<sane::test::MsgC>::try_into(orig).err().unwrap();
+    assert_eq!(error.path, vec!["f1", "f1"]);
+    assert_eq!(error.to_string(), "f1.f1: field is required");
+    assert!(std::error::Error::source(&error).is_none());
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/custom_types/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::custom_types::generated)
+        .with_this_mod(crate::positive::custom_types::sane)
+        .with_struct(Order, [total])
+        .with_field_type(
+            Order.id,
+            u64,
+            try_from = crate::positive::custom_types::parse_id,
+            into = ToString::to_string
+        )
+        .with_field_type(
+            Order.email,
+            crate::positive::custom_types::Email,
+            try_from = |value: String| crate::positive::custom_types::Email::parse(value),
+            into = crate::positive::custom_types::Email::to_string
+        )
+        .with_field_type(
+            Order.tags,
+            crate::positive::custom_types::Email,
+            try_from = crate::positive::custom_types::Email::parse,
+            into = crate::positive::custom_types::Email::to_string
+        )
+        .with_type_mapping(
+            test::Money,
+            i64,
+            try_from = crate::positive::custom_types::money_to_cents,
+            into = crate::positive::custom_types::cents_to_money
+        ));
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Email(String);
+
+impl Email {
+    pub fn parse(value: String) -> Result<Self, InvalidEmail> {
+        if value.contains('@') {
+            Ok(Self(value))
+        } else {
+            Err(InvalidEmail)
+        }
+    }
+}
+
+impl std::fmt::Display for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidEmail;
+
+impl std::fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid email")
+    }
+}
+
+impl std::error::Error for InvalidEmail {}
+
+pub fn parse_id(value: String) -> Result<u64, std::num::ParseIntError> {
+    value.parse()
+}
+
+pub fn money_to_cents(value: generated::test::Money) -> Result<i64, InvalidMoney> {
+    if !(0..100).contains(&value.cents) {
+        return Err(InvalidMoney);
+    }
+    Ok(value.units * 100 + i64::from(value.cents))
+}
+
+pub fn cents_to_money(value: &i64) -> generated::test::Money {
+    generated::test::Money {
+        units: value / 100,
+        cents: (value % 100) as i32,
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidMoney;
+
+impl std::fmt::Display for InvalidMoney {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid money")
+    }
+}
+
+impl std::error::Error for InvalidMoney {}
+
+fn money(units: i64, cents: i32) -> generated::test::Money {
+    generated::test::Money { units, cents }
+}
+
+fn original() -> generated::test::Order {
+    generated::test::Order {
+        id: "42".to_string(),
+        email: Some("a@b".to_string()),
+        tags: vec!["c@d".to_string()],
+        total: Some(money(1, 50)),
+        prices: HashMap::from([("e".to_string(), money(0, 1))]),
+        discount: Some(generated::test::order::Discount::Amount(money(2, 0))),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    assert_eq!(sane.id, 42);
+    assert_eq!(sane.email, Some(Email("a@b".to_string())));
+    assert_eq!(sane.tags, vec![Email("c@d".to_string())]);
+    assert_eq!(sane.total, 150);
+    assert_eq!(sane.prices["e"], 1);
+    assert!(matches!(
+        sane.discount,
+        Some(sane::test::order::Discount::Amount(200))
+    ));
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_error_field_type() {
+    let orig = generated::test::Order {
+        id: "x".to_string(),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "id: invalid digit found in string");
+    assert!(std::error::Error::source(&error)
+        .unwrap()
+        .is::<std::num::ParseIntError>());
+
+    let orig = generated::test::Order {
+        tags: vec!["c@d".to_string(), "e".to_string()],
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "tags: invalid email");
+}
+
+#[test]
+fn test_error_type_mapping() {
+    let orig = generated::test::Order {
+        discount: Some(generated::test::order::Discount::Amount(money(0, 100))),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "discount.Amount: invalid money");
+
+    let orig = generated::test::Order {
+        total: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "total: field is required");
+}
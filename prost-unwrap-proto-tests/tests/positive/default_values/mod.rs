@@ -0,0 +1,94 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/default_values/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::default_values::generated)
+        .with_this_mod(crate::positive::default_values::sane)
+        .with_timestamp(std)
+        .with_struct(
+            ListReq,
+            [
+                page = default,
+                retry = crate::positive::default_values::defaults::retry(),
+                since = default,
+                filter_page
+            ]
+        ));
+    }
+}
+
+pub mod defaults {
+    use super::generated::test::RetryPolicy;
+
+    pub fn retry() -> RetryPolicy {
+        RetryPolicy { attempts: 3 }
+    }
+}
+
+fn original() -> generated::test::ListReq {
+    generated::test::ListReq {
+        page: Some(generated::test::Page {
+            size: 10,
+            token: "t".to_string(),
+        }),
+        retry: Some(generated::test::RetryPolicy { attempts: 5 }),
+        since: Some(prost_types::Timestamp {
+            seconds: 1,
+            nanos: 0,
+        }),
+        filter_page: Some(generated::test::Page::default()),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::ListReq = orig.clone().try_into().unwrap();
+    let _: &sane::test::Page = &sane.page;
+    let _: &std::time::SystemTime = &sane.since;
+    assert_eq!(sane.page.size, 10);
+    assert_eq!(sane.retry.attempts, 5);
+    assert_eq!(orig, Into::<generated::test::ListReq>::into(sane));
+}
+
+#[test]
+fn test_conversion_default() {
+    let orig = generated::test::ListReq {
+        page: None,
+        retry: None,
+        since: None,
+        ..original()
+    };
+    let sane: sane::test::ListReq = orig.try_into().unwrap();
+    assert_eq!(sane.page.size, 0);
+    assert_eq!(sane.page.token, "");
+    assert_eq!(sane.retry.attempts, 3);
+    assert_eq!(sane.since, std::time::UNIX_EPOCH);
+
+    let orig: generated::test::ListReq = sane.into();
+    assert_eq!(orig.page, Some(generated::test::Page::default()));
+    assert_eq!(
+        orig.retry,
+        Some(generated::test::RetryPolicy { attempts: 3 })
+    );
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::ListReq {
+        filter_page: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::ListReq>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "filter_page: field is required");
+}
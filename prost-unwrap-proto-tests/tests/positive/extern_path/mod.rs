@@ -0,0 +1,73 @@
+// stands for the crate providing the `common` package items and mirrors
+pub mod common_crate {
+    pub mod generated {
+        pub mod common {
+            include!(".proto_out/common.rs");
+        }
+    }
+
+    pub mod sane {
+        pub mod common {
+            prost_unwrap::include!(from_source(
+                common,
+                "prost-unwrap-proto-tests/tests/positive/extern_path/.proto_out/common.rs"
+            )
+            .with_original_mod(crate::positive::extern_path::common_crate::generated)
+            .with_this_mod(crate::positive::extern_path::common_crate::sane)
+            .with_struct(MsgB, [f1]));
+        }
+    }
+}
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/extern_path/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::extern_path::generated)
+        .with_this_mod(crate::positive::extern_path::sane)
+        .with_extern(
+            crate::positive::extern_path::common_crate::generated
+                => crate::positive::extern_path::common_crate::sane
+        )
+        .with_struct(MsgC, [f1]));
+    }
+}
+
+use common_crate::generated::common as generated_common;
+use common_crate::sane::common as sane_common;
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::MsgC {
+        f1: Some(generated_common::MsgB {
+            f1: Some(generated_common::MsgA { f1: 1 }),
+        }),
+        f2: vec![generated_common::MsgB {
+            f1: Some(generated_common::MsgA { f1: 2 }),
+        }],
+    };
+    let sane: sane::test::MsgC = orig.clone().try_into().unwrap();
+    let _: &sane_common::MsgA = &sane.f1.f1;
+    let _: &sane_common::MsgA = &sane.f2[0].f1;
+    assert_eq!(orig, Into::<generated::test::MsgC>::into(sane));
+}
+
+#[test]
+fn test_error() {
+    let orig = generated::test::MsgC {
+        f1: Some(generated_common::MsgB {
+            f1: Some(generated_common::MsgA { f1: 1 }),
+        }),
+        f2: vec![generated_common::MsgB { f1: None }],
+    };
+    let error = TryInto::<sane::test::MsgC>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "f2.f1: field is required");
+}
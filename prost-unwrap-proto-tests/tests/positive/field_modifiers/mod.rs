@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/field_modifiers/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::field_modifiers::generated)
+        .with_this_mod(crate::positive::field_modifiers::sane)
+        .with_struct(
+            Order,
+            [
+                name = non_empty,
+                items = non_empty_vec,
+                id = non_zero,
+                tags = non_empty,
+                notes = non_empty,
+                primary,
+                amount = non_zero
+            ]
+        ));
+    }
+}
+
+fn original() -> generated::test::Order {
+    generated::test::Order {
+        name: "order".to_string(),
+        items: vec![
+            generated::test::Item {
+                sku: "a".to_string(),
+            },
+            generated::test::Item {
+                sku: "b".to_string(),
+            },
+        ],
+        id: 1,
+        tags: HashMap::from([("tag".to_string(), 1)]),
+        notes: vec!["note".to_string()],
+        primary: Some(generated::test::Item {
+            sku: "c".to_string(),
+        }),
+        amount: 0.5,
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    let _: &prost_unwrap::NonEmpty<Vec<sane::test::Item>> = &sane.items;
+    let _: &Vec<String> = &sane.notes;
+    let _: &sane::test::Item = &sane.primary;
+    assert_eq!(sane.name, "order");
+    assert_eq!(sane.items.len(), 2);
+    assert_eq!(sane.items.first().sku, "a");
+    assert_eq!(sane.items.last().sku, "b");
+    assert_eq!(sane.id, 1);
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_error_empty_string() {
+    let orig = generated::test::Order {
+        name: String::new(),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "name: field is empty");
+}
+
+#[test]
+fn test_error_empty_vec() {
+    let orig = generated::test::Order {
+        items: vec![],
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "items: field is empty");
+
+    let orig = generated::test::Order {
+        notes: vec![],
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "notes: field is empty");
+}
+
+#[test]
+fn test_error_empty_hashmap() {
+    let orig = generated::test::Order {
+        tags: HashMap::new(),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "tags: field is empty");
+}
+
+#[test]
+fn test_error_zero() {
+    let orig = generated::test::Order {
+        id: 0,
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "id: field is zero");
+
+    let orig = generated::test::Order {
+        amount: 0.0,
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "amount: field is zero");
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Order {
+        primary: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "primary: field is required");
+}
+
+#[test]
+fn test_non_empty() {
+    assert!(prost_unwrap::NonEmpty::<Vec<i32>>::new(vec![]).is_none());
+    let non_empty = prost_unwrap::NonEmpty::new(vec![1, 2]).unwrap();
+    assert_eq!(*non_empty.first(), 1);
+    assert_eq!(non_empty.iter().sum::<i32>(), 3);
+    assert_eq!(Vec::from(non_empty), vec![1, 2]);
+}
@@ -0,0 +1,70 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/flattened_fields/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::flattened_fields::generated)
+        .with_this_mod(crate::positive::flattened_fields::sane)
+        .with_struct(Address, [geo])
+        .with_struct(Team, [lead])
+        .with_flatten(Person.name)
+        .with_flatten(Person.address, prefix = ""));
+    }
+}
+
+fn person() -> generated::test::Person {
+    generated::test::Person {
+        name: Some(generated::test::Name {
+            first: "Ada".to_string(),
+            last: "Lovelace".to_string(),
+        }),
+        age: 36,
+        address: Some(generated::test::Address {
+            city: "London".to_string(),
+            geo: Some(generated::test::Geo {
+                lat: 51.5,
+                lon: -0.1,
+            }),
+        }),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::Team {
+        lead: Some(person()),
+    };
+    let sane: sane::test::Team = orig.clone().try_into().unwrap();
+    assert_eq!(sane.lead.name_first, "Ada");
+    assert_eq!(sane.lead.name_last, "Lovelace");
+    assert_eq!(sane.lead.age, 36);
+    assert_eq!(sane.lead.city, "London");
+    let _: &sane::test::Geo = &sane.lead.geo;
+    assert_eq!(sane.lead.geo.lat, 51.5);
+    assert_eq!(orig, Into::<generated::test::Team>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Person {
+        name: None,
+        ..person()
+    };
+    let error = TryInto::<sane::test::Person>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "name: field is required");
+}
+
+#[test]
+fn test_error_nested() {
+    let mut orig = person();
+    orig.address.as_mut().unwrap().geo = None;
+    let error = TryInto::<sane::test::Person>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "address.geo: field is required");
+}
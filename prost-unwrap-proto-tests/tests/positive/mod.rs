@@ -1,11 +1,36 @@
+mod any_payload;
+mod cross_package;
+mod custom_types;
+mod default_values;
+mod extern_path;
+mod field_modifiers;
+mod flattened_fields;
 mod hashmap_of_scalars;
 mod hashmap_of_structs;
+mod named_views;
 mod nested_enum;
+mod nested_field_paths;
+mod nested_field_paths_base;
 mod nested_struct;
+mod newtypes;
 mod no_modifications;
 mod oneof_enums;
+mod oneof_message_enum;
 mod oneof_scalars;
 mod oneof_structs;
+mod oneof_variant;
+mod optional_fields;
+mod private_fields;
+mod renamed_items;
 mod repeated_enum;
 mod repeated_scalar;
 mod repeated_struct;
+mod required_field_policies;
+mod skipped_fields;
+mod transparent_wrappers;
+mod typed_builders;
+mod validate_rules;
+mod validators;
+mod well_known_json;
+mod well_known_time;
+mod wrapper_types;
@@ -0,0 +1,84 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/named_views/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::named_views::generated)
+        .with_this_mod(crate::positive::named_views::sane)
+        .with_struct(User as UserForCreate, [email])
+        .with_struct(User as UserForUpdate, [id])
+        .with_struct(CreateUserRequest, [user as UserForCreate])
+        .with_struct(UpdateUserRequest, [user as UserForUpdate]));
+    }
+}
+
+fn user() -> generated::test::User {
+    generated::test::User {
+        id: Some(generated::test::UserId { value: 1 }),
+        email: Some(generated::test::Email {
+            value: "a@b.c".to_string(),
+        }),
+        name: "a".to_string(),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::CreateUserRequest { user: Some(user()) };
+    let sane: sane::test::CreateUserRequest = orig.clone().try_into().unwrap();
+    let _: &sane::test::UserForCreate = &sane.user;
+    let _: &sane::test::Email = &sane.user.email;
+    let _: &Option<sane::test::UserId> = &sane.user.id;
+    assert_eq!(sane.user.email.value, "a@b.c");
+    assert_eq!(orig, Into::<generated::test::CreateUserRequest>::into(sane));
+
+    let orig = generated::test::UpdateUserRequest { user: Some(user()) };
+    let sane: sane::test::UpdateUserRequest = orig.clone().try_into().unwrap();
+    let _: &sane::test::UserForUpdate = &sane.user;
+    let _: &sane::test::UserId = &sane.user.id;
+    let _: &Option<sane::test::Email> = &sane.user.email;
+    assert_eq!(sane.user.id.value, 1);
+    assert_eq!(orig, Into::<generated::test::UpdateUserRequest>::into(sane));
+}
+
+#[test]
+fn test_conversion_unspecified() {
+    let orig = generated::test::User {
+        id: None,
+        email: None,
+        ..user()
+    };
+    let sane: sane::test::User = orig.clone().try_into().unwrap();
+    assert!(sane.id.is_none());
+    assert!(sane.email.is_none());
+    assert_eq!(orig, Into::<generated::test::User>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::CreateUserRequest {
+        user: Some(generated::test::User {
+            email: None,
+            ..user()
+        }),
+    };
+    let error = TryInto::<sane::test::CreateUserRequest>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "user.email: field is required");
+
+    let orig = generated::test::UpdateUserRequest {
+        user: Some(generated::test::User { id: None, ..user() }),
+    };
+    let error = TryInto::<sane::test::UpdateUserRequest>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "user.id: field is required");
+}
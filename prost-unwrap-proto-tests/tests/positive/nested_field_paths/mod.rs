@@ -0,0 +1,82 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/nested_field_paths/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::nested_field_paths::generated)
+        .with_this_mod(crate::positive::nested_field_paths::sane)
+        .with_struct(CreateOrder, [order, order.customer, order.customer.address]));
+    }
+}
+
+fn create_order() -> generated::test::CreateOrder {
+    generated::test::CreateOrder {
+        order: Some(generated::test::Order {
+            id: "1".to_string(),
+            customer: Some(generated::test::Customer {
+                name: "a".to_string(),
+                address: Some(generated::test::Address {
+                    city: "b".to_string(),
+                    street: "c".to_string(),
+                }),
+                billing_address: None,
+            }),
+        }),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = create_order();
+    let sane: sane::test::CreateOrder = orig.clone().try_into().unwrap();
+    let _: &sane::test::CreateOrderOrder = &sane.order;
+    let _: &sane::test::CreateOrderOrderCustomer = &sane.order.customer;
+    let _: &sane::test::Address = &sane.order.customer.address;
+    let _: &Option<sane::test::Address> = &sane.order.customer.billing_address;
+    assert_eq!(sane.order.customer.address.city, "b");
+    assert_eq!(orig, Into::<generated::test::CreateOrder>::into(sane));
+}
+
+#[test]
+fn test_conversion_unspecified() {
+    let orig = generated::test::Order {
+        id: "1".to_string(),
+        customer: None,
+    };
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    assert!(sane.customer.is_none());
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let mut orig = create_order();
+    orig.order
+        .as_mut()
+        .unwrap()
+        .customer
+        .as_mut()
+        .unwrap()
+        .address = None;
+    let error = TryInto::<sane::test::CreateOrder>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "order.customer.address: field is required"
+    );
+
+    let mut orig = create_order();
+    orig.order.as_mut().unwrap().customer = None;
+    let error = TryInto::<sane::test::CreateOrder>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "order.customer: field is required");
+}
@@ -0,0 +1,116 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/nested_field_paths_base/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::nested_field_paths_base::generated)
+        .with_this_mod(crate::positive::nested_field_paths_base::sane)
+        .with_struct(
+            Order,
+            [id = non_empty, shipping_address, billing_address = default]
+        )
+        .with_struct(CreateOrder, [order, order.customer]));
+    }
+}
+
+fn order() -> generated::test::Order {
+    generated::test::Order {
+        id: "1".to_string(),
+        customer: Some(generated::test::Customer {
+            name: "a".to_string(),
+            address: None,
+        }),
+        shipping_address: Some(generated::test::Address {
+            city: "b".to_string(),
+        }),
+        billing_address: Some(generated::test::Address {
+            city: "c".to_string(),
+        }),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::CreateOrder {
+        order: Some(order()),
+    };
+    let sane: sane::test::CreateOrder = orig.clone().try_into().unwrap();
+    let _: &sane::test::CreateOrderOrder = &sane.order;
+    let _: &sane::test::Customer = &sane.order.customer;
+    let _: &sane::test::Address = &sane.order.shipping_address;
+    let _: &sane::test::Address = &sane.order.billing_address;
+    assert_eq!(sane.order.id, "1");
+    assert_eq!(sane.order.customer.name, "a");
+    assert_eq!(sane.order.shipping_address.city, "b");
+    assert_eq!(orig, Into::<generated::test::CreateOrder>::into(sane));
+}
+
+#[test]
+fn test_conversion_base() {
+    let orig = generated::test::Order {
+        customer: None,
+        ..order()
+    };
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    assert!(sane.customer.is_none());
+    assert_eq!(sane.shipping_address.city, "b");
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_conversion_default() {
+    let orig = generated::test::CreateOrder {
+        order: Some(generated::test::Order {
+            billing_address: None,
+            ..order()
+        }),
+    };
+    let sane: sane::test::CreateOrder = orig.try_into().unwrap();
+    assert_eq!(sane.order.billing_address.city, "");
+}
+
+#[test]
+fn test_error() {
+    let orig = generated::test::CreateOrder {
+        order: Some(generated::test::Order {
+            id: String::new(),
+            ..order()
+        }),
+    };
+    let error = TryInto::<sane::test::CreateOrder>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "order.id: field is empty");
+
+    let orig = generated::test::CreateOrder {
+        order: Some(generated::test::Order {
+            shipping_address: None,
+            ..order()
+        }),
+    };
+    let error = TryInto::<sane::test::CreateOrder>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "order.shipping_address: field is required"
+    );
+
+    let orig = generated::test::CreateOrder {
+        order: Some(generated::test::Order {
+            customer: None,
+            ..order()
+        }),
+    };
+    let error = TryInto::<sane::test::CreateOrder>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "order.customer: field is required");
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/newtypes/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::newtypes::generated)
+        .with_this_mod(crate::positive::newtypes::sane)
+        .with_newtype(User.id, UserId)
+        .with_newtype(User.friend_ids, UserId)
+        .with_newtype(User.balance_cents, Cents)
+        .with_newtype(Order.id, OrderId)
+        .with_newtype(Order.user_id, UserId)
+        .with_newtype(Order.amount_cents, Cents)
+        .with_newtype(Order.item_cents, Cents)
+        .with_newtype(order::Line.amount_cents, Cents));
+    }
+}
+
+use sane::test::Cents;
+use sane::test::OrderId;
+use sane::test::UserId;
+
+fn original_user() -> generated::test::User {
+    generated::test::User {
+        id: "u1".to_string(),
+        friend_ids: vec!["u2".to_string()],
+        balance_cents: Some(100),
+    }
+}
+
+fn original_order() -> generated::test::Order {
+    generated::test::Order {
+        id: "o1".to_string(),
+        user_id: "u1".to_string(),
+        amount_cents: 300,
+        item_cents: HashMap::from([("i1".to_string(), 200)]),
+        lines: vec![generated::test::order::Line { amount_cents: 100 }],
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original_user();
+    let sane: sane::test::User = orig.clone().try_into().unwrap();
+    assert_eq!(sane.id, UserId("u1".to_string()));
+    assert_eq!(sane.friend_ids, vec![UserId("u2".to_string())]);
+    assert_eq!(sane.balance_cents, Some(Cents(100)));
+    assert_eq!(orig, Into::<generated::test::User>::into(sane));
+
+    let orig = original_order();
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    assert_eq!(sane.id, OrderId("o1".to_string()));
+    assert_eq!(sane.user_id, UserId("u1".to_string()));
+    assert_eq!(sane.amount_cents, Cents(300));
+    assert_eq!(sane.item_cents["i1"], Cents(200));
+    assert_eq!(sane.lines[0].amount_cents, Cents(100));
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_newtype_traits() {
+    let user_id = UserId::from_str("u1").unwrap();
+    assert_eq!(user_id.to_string(), "u1");
+    assert_eq!(AsRef::<String>::as_ref(&user_id), "u1");
+    assert_eq!(String::from(user_id.clone()), "u1");
+    assert_eq!(UserId::from("u1".to_string()), user_id);
+
+    let cents: Cents = "42".parse().unwrap();
+    assert_eq!(cents, Cents(42));
+    assert_eq!(i64::from(cents), 42);
+    assert!(Cents::from_str("x").is_err());
+}
@@ -0,0 +1,62 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/oneof_message_enum/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::oneof_message_enum::generated)
+        .with_this_mod(crate::positive::oneof_message_enum::sane)
+        .with_oneof_enum(Command)
+        .with_struct(Envelope, [command]));
+    }
+}
+
+fn original() -> generated::test::Envelope {
+    generated::test::Envelope {
+        command: Some(generated::test::Command {
+            kind: Some(generated::test::command::Kind::Create(
+                generated::test::Create {
+                    name: "a".to_string(),
+                },
+            )),
+        }),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Envelope = orig.clone().try_into().unwrap();
+    match &sane.command {
+        sane::test::Command::Create(create) => assert_eq!(create.name, "a"),
+        _ => panic!("expected `Create` variant"),
+    }
+    assert_eq!(orig, Into::<generated::test::Envelope>::into(sane));
+}
+
+#[test]
+fn test_conversion_scalar() {
+    let orig = generated::test::Command {
+        kind: Some(generated::test::command::Kind::Ping(7)),
+    };
+    let sane: sane::test::Command = orig.clone().try_into().unwrap();
+    assert!(matches!(sane, sane::test::Command::Ping(7)));
+    assert_eq!(orig, Into::<generated::test::Command>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Envelope {
+        command: Some(generated::test::Command { kind: None }),
+    };
+    let error = TryInto::<sane::test::Envelope>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "command.kind: field is required");
+}
@@ -0,0 +1,66 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/oneof_variant/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::oneof_variant::generated)
+        .with_this_mod(crate::positive::oneof_variant::sane)
+        .with_struct(Req, [payload = variant(Create)])
+        .with_struct(Ping, [payload = variant(Seq)]));
+    }
+}
+
+fn original() -> generated::test::Req {
+    generated::test::Req {
+        payload: Some(generated::test::req::Payload::Create(
+            generated::test::Create {
+                name: "a".to_string(),
+            },
+        )),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Req = orig.clone().try_into().unwrap();
+    let _: &sane::test::Create = &sane.payload;
+    assert_eq!(sane.payload.name, "a");
+    assert_eq!(orig, Into::<generated::test::Req>::into(sane));
+}
+
+#[test]
+fn test_conversion_scalar() {
+    let orig = generated::test::Ping {
+        payload: Some(generated::test::ping::Payload::Seq(7)),
+    };
+    let sane: sane::test::Ping = orig.clone().try_into().unwrap();
+    let _: &i64 = &sane.payload;
+    assert_eq!(sane.payload, 7);
+    assert_eq!(orig, Into::<generated::test::Ping>::into(sane));
+}
+
+#[test]
+fn test_error_other_variant() {
+    let orig = generated::test::Req {
+        payload: Some(generated::test::req::Payload::Delete(
+            generated::test::Delete { id: 1 },
+        )),
+    };
+    let error = TryInto::<sane::test::Req>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "payload: expected `Create` variant");
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Req { payload: None };
+    let error = TryInto::<sane::test::Req>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "payload: field is required");
+}
@@ -0,0 +1,59 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/optional_fields/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::optional_fields::generated)
+        .with_this_mod(crate::positive::optional_fields::sane)
+        .with_newtype(Profile.referrer_id, UserId)
+        .with_optional(Profile, [nickname, limit, status, referrer_id]));
+    }
+}
+
+use sane::test::UserId;
+
+fn original() -> generated::test::Profile {
+    generated::test::Profile {
+        id: "u1".to_string(),
+        nickname: "nick".to_string(),
+        limit: 10,
+        status: generated::test::Status::Active as i32,
+        referrer_id: "u2".to_string(),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Profile = orig.clone().try_into().unwrap();
+    let _: &String = &sane.id;
+    let _: &Option<String> = &sane.nickname;
+    let _: &Option<i32> = &sane.limit;
+    let _: &Option<UserId> = &sane.referrer_id;
+    assert_eq!(sane.nickname.as_deref(), Some("nick"));
+    assert_eq!(sane.limit, Some(10));
+    assert_eq!(sane.status, Some(generated::test::Status::Active as i32));
+    assert_eq!(sane.referrer_id, Some(UserId("u2".to_string())));
+    assert_eq!(orig, Into::<generated::test::Profile>::into(sane));
+}
+
+#[test]
+fn test_conversion_default() {
+    let orig = generated::test::Profile {
+        id: "u1".to_string(),
+        ..Default::default()
+    };
+    let sane: sane::test::Profile = orig.clone().try_into().unwrap();
+    assert_eq!(sane.nickname, None);
+    assert_eq!(sane.limit, None);
+    assert_eq!(sane.status, None);
+    assert_eq!(sane.referrer_id, None);
+    assert_eq!(orig, Into::<generated::test::Profile>::into(sane));
+}
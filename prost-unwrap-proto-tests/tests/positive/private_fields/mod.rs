@@ -0,0 +1,139 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/private_fields/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::private_fields::generated)
+        .with_this_mod(crate::positive::private_fields::sane)
+        .with_struct(Account, [balance, id = non_empty])
+        .with_struct(Bank, [accounts = non_empty_vec])
+        .with_private_fields(Account)
+        .with_private_fields(Bank)
+        .with_validator(Account, crate::positive::private_fields::validate::account)
+        .with_validator(
+            Account,
+            crate::positive::private_fields::validate::account_id,
+            original
+        ));
+    }
+}
+
+pub mod validate {
+    use super::generated;
+    use super::sane;
+
+    pub fn account(account: &sane::test::Account) -> Result<(), &'static str> {
+        match account.credit_limit() {
+            Some(credit_limit) if credit_limit.units < 0 => {
+                Err("credit_limit must not be negative")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn account_id(account: &generated::test::Account) -> Result<(), &'static str> {
+        if account.id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Ok(())
+        } else {
+            Err("id must be alphanumeric")
+        }
+    }
+}
+
+fn account() -> generated::test::Account {
+    generated::test::Account {
+        id: "a1".to_string(),
+        balance: Some(generated::test::Money { units: 10 }),
+        credit_limit: None,
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::Bank {
+        accounts: vec![account()],
+    };
+    let sane: sane::test::Bank = orig.clone().try_into().unwrap();
+    let account = sane.accounts().first();
+    assert_eq!(account.id(), "a1");
+    assert_eq!(account.balance().units, 10);
+    assert!(account.credit_limit().is_none());
+    assert_eq!(orig, Into::<generated::test::Bank>::into(sane));
+}
+
+#[test]
+fn test_new() {
+    let sane =
+        sane::test::Account::new("a1".to_string(), sane::test::Money { units: 10 }, None).unwrap();
+    assert_eq!(
+        account(),
+        Into::<generated::test::Account>::into(sane.clone())
+    );
+
+    let (id, balance, credit_limit) = sane.into_parts();
+    assert_eq!(id, "a1");
+    assert_eq!(balance.units, 10);
+    assert!(credit_limit.is_none());
+}
+
+#[test]
+fn test_new_error_modifier() {
+    let error = sane::test::Account::new(String::new(), sane::test::Money { units: 10 }, None)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "id: field is empty");
+}
+
+#[test]
+fn test_new_error_validator() {
+    let error = sane::test::Account::new(
+        "a1".to_string(),
+        sane::test::Money { units: 10 },
+        Some(sane::test::Money { units: -1 }),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(error.to_string(), "credit_limit must not be negative");
+    assert_eq!(
+        std::error::Error::source(&error).unwrap().to_string(),
+        "credit_limit must not be negative"
+    );
+}
+
+#[test]
+fn test_new_error_original_validator() {
+    let error = sane::test::Account::new("a 1".to_string(), sane::test::Money { units: 10 }, None)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "id must be alphanumeric");
+
+    let error = TryInto::<sane::test::Account>::try_into(generated::test::Account {
+        id: "a 1".to_string(),
+        ..account()
+    })
+    .err()
+    .unwrap();
+    assert_eq!(error.to_string(), "id must be alphanumeric");
+}
+
+#[test]
+fn test_new_non_empty_vec() {
+    let account: sane::test::Account = account().try_into().unwrap();
+    let sane = sane::test::Bank::new(prost_unwrap::NonEmpty::new(vec![account]).unwrap()).unwrap();
+    assert_eq!(sane.accounts().first().id(), "a1");
+
+    let error = TryInto::<sane::test::Bank>::try_into(generated::test::Bank {
+        accounts: Vec::new(),
+    })
+    .err()
+    .unwrap();
+    assert_eq!(error.to_string(), "accounts: field is empty");
+    assert!(prost_unwrap::NonEmpty::<Vec<sane::test::Account>>::new(Vec::new()).is_none());
+}
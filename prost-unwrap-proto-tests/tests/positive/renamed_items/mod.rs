@@ -0,0 +1,83 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/renamed_items/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::renamed_items::generated)
+        .with_this_mod(crate::positive::renamed_items::sane)
+        .with_rename(PbUser, User)
+        .with_rename(PbMoney, Money)
+        .with_rename(PbStatus, Status)
+        .with_struct(
+            PbUser,
+            [balance],
+            rename = { pb_user_identifier => user_id, r#type => kind }
+        )
+        .with_struct(Envelope, [user]));
+    }
+}
+
+fn user() -> generated::test::PbUser {
+    generated::test::PbUser {
+        pb_user_identifier: "1".to_string(),
+        r#type: "a".to_string(),
+        balance: Some(generated::test::PbMoney { units: 10 }),
+        status: generated::test::PbStatus::Active as i32,
+        contact: Some(generated::test::pb_user::Contact::Deposit(
+            generated::test::PbMoney { units: 20 },
+        )),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = generated::test::Envelope {
+        user: Some(user()),
+        users: vec![user()],
+    };
+    let sane: sane::test::Envelope = orig.clone().try_into().unwrap();
+    let _: &sane::test::User = &sane.user;
+    let _: &Vec<sane::test::User> = &sane.users;
+    let _: &sane::test::Money = &sane.user.balance;
+    assert_eq!(sane.user.user_id, "1");
+    assert_eq!(sane.user.kind, "a");
+    assert!(matches!(
+        sane.user.contact,
+        Some(sane::test::pb_user::Contact::Deposit(sane::test::Money {
+            units: 20
+        }))
+    ));
+    assert_eq!(orig, Into::<generated::test::Envelope>::into(sane));
+}
+
+#[test]
+fn test_conversion_enum() {
+    let sane: sane::test::Status = generated::test::PbStatus::Active.try_into().unwrap();
+    assert_eq!(sane, sane::test::Status::Active);
+    assert_eq!(
+        generated::test::PbStatus::Active,
+        Into::<generated::test::PbStatus>::into(sane)
+    );
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Envelope {
+        user: Some(generated::test::PbUser {
+            balance: None,
+            ..user()
+        }),
+        users: vec![],
+    };
+    let error = TryInto::<sane::test::Envelope>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "user.balance: field is required");
+}
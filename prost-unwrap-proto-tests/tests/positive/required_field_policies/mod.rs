@@ -0,0 +1,102 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod wildcard {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/required_field_policies/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::required_field_policies::generated)
+        .with_this_mod(crate::positive::required_field_policies::wildcard)
+        .with_struct(Order, [*, !legacy_total]));
+    }
+}
+
+pub mod policy {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/required_field_policies/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::required_field_policies::generated)
+        .with_this_mod(crate::positive::required_field_policies::policy)
+        .with_default_policy(require_messages)
+        .with_struct(Item, [name = non_empty]));
+    }
+}
+
+fn order() -> generated::test::Order {
+    generated::test::Order {
+        id: "1".to_string(),
+        total: Some(generated::test::Money { units: 10 }),
+        item: Some(generated::test::Item {
+            name: "a".to_string(),
+            price: None,
+        }),
+        legacy_total: Some(generated::test::Money { units: 10 }),
+        count: None,
+        payment: Some(generated::test::order::Payment::Card("b".to_string())),
+    }
+}
+
+#[test]
+fn test_wildcard_conversion() {
+    let orig = order();
+    let sane: wildcard::test::Order = orig.clone().try_into().unwrap();
+    let _: &wildcard::test::Money = &sane.total;
+    let _: &wildcard::test::Item = &sane.item;
+    let _: &Option<wildcard::test::Money> = &sane.legacy_total;
+    let _: &Option<i32> = &sane.count;
+    let _: &Option<wildcard::test::order::Payment> = &sane.payment;
+    let _: &Option<wildcard::test::Money> = &sane.item.price;
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+
+    let orig = generated::test::Order {
+        legacy_total: None,
+        ..order()
+    };
+    let sane: wildcard::test::Order = orig.clone().try_into().unwrap();
+    assert!(sane.legacy_total.is_none());
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_wildcard_error_required() {
+    let orig = generated::test::Order {
+        item: None,
+        ..order()
+    };
+    let error = TryInto::<wildcard::test::Order>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "item: field is required");
+}
+
+#[test]
+fn test_policy_conversion() {
+    let orig = order();
+    let sane: policy::test::Order = orig.clone().try_into().unwrap();
+    let _: &policy::test::Money = &sane.total;
+    let _: &policy::test::Item = &sane.item;
+    let _: &policy::test::Money = &sane.legacy_total;
+    let _: &Option<i32> = &sane.count;
+    // the explicitly specified struct is not affected by the policy
+    let _: &Option<policy::test::Money> = &sane.item.price;
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_policy_error_required() {
+    let orig = generated::test::Order {
+        legacy_total: None,
+        ..order()
+    };
+    let error = TryInto::<policy::test::Order>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "legacy_total: field is required");
+}
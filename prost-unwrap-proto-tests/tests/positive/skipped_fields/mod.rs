@@ -0,0 +1,66 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/skipped_fields/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::skipped_fields::generated)
+        .with_this_mod(crate::positive::skipped_fields::sane)
+        .with_struct(
+            Account,
+            [id = non_empty],
+            skip = [debug_info, legacy_id = reject, deprecated()]
+        ));
+    }
+}
+
+#[allow(deprecated)]
+fn account() -> generated::test::Account {
+    generated::test::Account {
+        id: "1".to_string(),
+        debug_info: Some(generated::test::DebugInfo {
+            trace: "a".to_string(),
+        }),
+        legacy_id: 0,
+        old_name: "b".to_string(),
+        old_rank: 1,
+        note: "c".to_string(),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let sane: sane::test::Account = account().try_into().unwrap();
+    // the mirror struct has the not skipped fields only
+    let sane::test::Account { id, note } = sane.clone();
+    assert_eq!(id, "1");
+    assert_eq!(note, "c");
+
+    let orig = Into::<generated::test::Account>::into(sane);
+    assert_eq!(
+        orig,
+        generated::test::Account {
+            id: "1".to_string(),
+            note: "c".to_string(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_error_rejected() {
+    let orig = generated::test::Account {
+        legacy_id: 1,
+        ..account()
+    };
+    let error = TryInto::<sane::test::Account>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "legacy_id: field must not be set");
+}
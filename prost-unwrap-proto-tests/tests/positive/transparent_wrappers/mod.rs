@@ -0,0 +1,72 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/transparent_wrappers/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::transparent_wrappers::generated)
+        .with_this_mod(crate::positive::transparent_wrappers::sane)
+        .with_transparent(OrderId)
+        .with_transparent(Tags)
+        .with_struct(Order, [id, tags]));
+    }
+}
+
+fn order_id(value: &str) -> generated::test::OrderId {
+    generated::test::OrderId {
+        value: value.to_string(),
+    }
+}
+
+fn original() -> generated::test::Order {
+    generated::test::Order {
+        id: Some(order_id("a")),
+        tags: Some(generated::test::Tags {
+            tags: vec!["b".to_string(), "c".to_string()],
+        }),
+        related: vec![order_id("d"), order_id("e")],
+        parent: None,
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    let _: &String = &sane.id;
+    let _: &Vec<String> = &sane.tags;
+    let _: &Vec<String> = &sane.related;
+    let _: &Option<String> = &sane.parent;
+    assert_eq!(sane.id, "a");
+    assert_eq!(sane.tags, vec!["b", "c"]);
+    assert_eq!(sane.related, vec!["d", "e"]);
+    assert_eq!(sane.parent, None);
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_conversion_optional() {
+    let orig = generated::test::Order {
+        parent: Some(order_id("f")),
+        ..original()
+    };
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    assert_eq!(sane.parent.as_deref(), Some("f"));
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Order {
+        id: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::Order>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "id: field is required");
+}
@@ -0,0 +1,75 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/typed_builders/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::typed_builders::generated)
+        .with_this_mod(crate::positive::typed_builders::sane)
+        .with_struct(Order, [customer, id = non_empty])
+        .with_struct(Parcel, [content])
+        .with_oneof_enum(Content)
+        .with_private_fields(Customer)
+        .with_builder(Customer)
+        .with_builder(Order)
+        .with_builder(Parcel));
+    }
+}
+
+fn customer() -> sane::test::Customer {
+    sane::test::Customer::builder()
+        .name("Ada".to_string())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_build() {
+    let order = sane::test::Order::builder()
+        .quantity(2)
+        .customer(customer())
+        .payment(sane::test::order::Payment::Voucher("v1".to_string()))
+        .id("o1".to_string())
+        .build();
+    let parcel = sane::test::Parcel::builder()
+        .content(sane::test::Content::Order(order))
+        .build();
+
+    let orig = generated::test::Parcel {
+        content: Some(generated::test::Content {
+            content: Some(generated::test::content::Content::Order(
+                generated::test::Order {
+                    id: "o1".to_string(),
+                    customer: Some(generated::test::Customer {
+                        name: "Ada".to_string(),
+                        email: String::new(),
+                    }),
+                    notes: vec![],
+                    referrer: None,
+                    payment: Some(generated::test::order::Payment::Voucher("v1".to_string())),
+                    quantity: 2,
+                },
+            )),
+        }),
+        label: String::new(),
+    };
+    assert_eq!(orig, Into::<generated::test::Parcel>::into(parcel));
+}
+
+#[test]
+fn test_build_defaults() {
+    let order = sane::test::Order::builder()
+        .id("o1".to_string())
+        .customer(customer())
+        .build();
+    assert!(order.notes.is_empty());
+    assert!(order.referrer.is_none());
+    assert!(order.payment.is_none());
+    assert_eq!(order.quantity, 0);
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/validate_rules/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::validate_rules::generated)
+        .with_this_mod(crate::positive::validate_rules::sane)
+        .with_validate_rules(
+            "prost-unwrap-proto-tests/tests/positive/validate_rules/.proto/test.proto"
+        )
+        .with_private_fields(Customer));
+    }
+}
+
+use generated::test::order::Line;
+
+fn original() -> generated::test::Order {
+    generated::test::Order {
+        id: "o1".to_string(),
+        name: "order".to_string(),
+        item: Some(generated::test::Item {
+            sku: "sku-1".to_string(),
+        }),
+        lines: vec![Line { amount: 1 }],
+        discount: 0.5,
+        limit: Some(100),
+        tags: HashMap::from([("tag".to_string(), 1)]),
+    }
+}
+
+fn error(orig: generated::test::Order) -> String {
+    TryInto::<sane::test::Order>::try_into(orig)
+        .err()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Order = orig.clone().try_into().unwrap();
+    let _: &sane::test::Item = &sane.item;
+    let _: &Option<u32> = &sane.limit;
+    assert_eq!(sane.item.sku, "sku-1");
+    assert_eq!(orig, Into::<generated::test::Order>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::Order {
+        item: None,
+        ..original()
+    };
+    assert_eq!(error(orig), "item: field is required");
+
+    let orig = generated::test::Order {
+        id: String::new(),
+        ..original()
+    };
+    assert_eq!(error(orig), "id: field is required");
+}
+
+#[test]
+fn test_error_string() {
+    let orig = generated::test::Order {
+        name: "long name".to_string(),
+        ..original()
+    };
+    assert_eq!(error(orig), "name: length must be at most 5");
+
+    let orig = generated::test::Order {
+        item: Some(generated::test::Item {
+            sku: "sku".to_string(),
+        }),
+        ..original()
+    };
+    assert_eq!(error(orig), "item.sku: length must be at least 5");
+
+    let orig = generated::test::Order {
+        item: Some(generated::test::Item {
+            sku: "item-1".to_string(),
+        }),
+        ..original()
+    };
+    assert_eq!(error(orig), "item.sku: must start with \"sku-\"");
+}
+
+#[test]
+fn test_error_number() {
+    let orig = generated::test::Order {
+        lines: vec![Line { amount: 1 }, Line { amount: 0 }],
+        ..original()
+    };
+    assert_eq!(error(orig), "lines.amount: must be greater than 0");
+
+    let orig = generated::test::Order {
+        discount: 1.0,
+        ..original()
+    };
+    assert_eq!(error(orig), "discount: must be less than 1");
+
+    let orig = generated::test::Order {
+        limit: Some(101),
+        ..original()
+    };
+    assert_eq!(error(orig), "limit: must be less than or equal to 100");
+
+    let orig = generated::test::Order {
+        limit: None,
+        ..original()
+    };
+    assert!(TryInto::<sane::test::Order>::try_into(orig).is_ok());
+}
+
+#[test]
+fn test_error_items() {
+    let orig = generated::test::Order {
+        lines: vec![],
+        ..original()
+    };
+    assert_eq!(error(orig), "lines: must contain at least 1 items");
+
+    let orig = generated::test::Order {
+        lines: vec![Line { amount: 1 }; 3],
+        ..original()
+    };
+    assert_eq!(error(orig), "lines: must contain at most 2 items");
+
+    let orig = generated::test::Order {
+        tags: HashMap::new(),
+        ..original()
+    };
+    assert_eq!(error(orig), "tags: must contain at least 1 items");
+}
+
+#[test]
+fn test_new() {
+    let item = sane::test::Item {
+        sku: "sku-1".to_string(),
+    };
+    let sane =
+        sane::test::Customer::new("c@example.com".to_string(), 18, item.clone(), None).unwrap();
+    assert_eq!(sane.item().sku, "sku-1");
+
+    let error = sane::test::Customer::new(String::new(), 18, item.clone(), None)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "email: field is required");
+
+    let error = sane::test::Customer::new("c@example.com".to_string(), 17, item.clone(), None)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "age: must be greater than 17");
+
+    let error =
+        sane::test::Customer::new("c@example.com".to_string(), 18, item, Some("c".to_string()))
+            .err()
+            .unwrap();
+    assert_eq!(error.to_string(), "nickname: length must be at least 2");
+}
@@ -0,0 +1,101 @@
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/validators/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::validators::generated)
+        .with_this_mod(crate::positive::validators::sane)
+        .with_struct(Trip, [booking])
+        .with_validator(Booking, crate::positive::validators::validate::booking)
+        .with_validator(Trip, crate::positive::validators::validate::trip, original));
+    }
+}
+
+pub mod validate {
+    use super::generated;
+    use super::sane;
+
+    pub fn booking(booking: &sane::test::Booking) -> Result<(), &'static str> {
+        if booking.start_time < booking.end_time {
+            Ok(())
+        } else {
+            Err("start_time must be before end_time")
+        }
+    }
+
+    pub fn trip(trip: &generated::test::Trip) -> Result<(), String> {
+        match (trip.flights.is_empty(), trip.trains.is_empty()) {
+            (false, true) | (true, false) => Ok(()),
+            _ => Err(format!(
+                "exactly one of flights and trains must be set, got {} flights and {} trains",
+                trip.flights.len(),
+                trip.trains.len()
+            )),
+        }
+    }
+}
+
+fn original() -> generated::test::Trip {
+    generated::test::Trip {
+        booking: Some(generated::test::Booking {
+            start_time: 1,
+            end_time: 2,
+        }),
+        flights: vec!["f1".to_string()],
+        trains: vec![],
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::Trip = orig.clone().try_into().unwrap();
+    assert_eq!(sane.booking.start_time, 1);
+    assert_eq!(orig, Into::<generated::test::Trip>::into(sane));
+}
+
+#[test]
+fn test_error_mirror_validator() {
+    let booking = generated::test::Booking {
+        start_time: 2,
+        end_time: 1,
+    };
+    let error = TryInto::<sane::test::Booking>::try_into(booking.clone())
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "start_time must be before end_time");
+    assert_eq!(
+        std::error::Error::source(&error).unwrap().to_string(),
+        "start_time must be before end_time"
+    );
+
+    let orig = generated::test::Trip {
+        booking: Some(booking),
+        ..original()
+    };
+    let error = TryInto::<sane::test::Trip>::try_into(orig).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "booking: start_time must be before end_time"
+    );
+}
+
+#[test]
+fn test_error_original_validator() {
+    let orig = generated::test::Trip {
+        trains: vec!["t1".to_string()],
+        ..original()
+    };
+    let error = TryInto::<sane::test::Trip>::try_into(orig).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "exactly one of flights and trains must be set, got 1 flights and 1 trains"
+    );
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use prost_types::value::Kind;
+use serde_json::json;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/well_known_json/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::well_known_json::generated)
+        .with_this_mod(crate::positive::well_known_json::sane)
+        .with_json(serde_json)
+        .with_struct(MsgA, [f1]));
+    }
+}
+
+fn value(kind: Kind) -> prost_types::Value {
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn original() -> generated::test::MsgA {
+    generated::test::MsgA {
+        f1: Some(prost_types::Struct {
+            fields: BTreeMap::from([
+                ("a".to_string(), value(Kind::NumberValue(1.5))),
+                (
+                    "b".to_string(),
+                    value(Kind::ListValue(prost_types::ListValue {
+                        values: vec![value(Kind::BoolValue(true)), value(Kind::NullValue(0))],
+                    })),
+                ),
+            ]),
+        }),
+        f2: Some(value(Kind::StringValue("c".to_string()))),
+        f3: None,
+        f4: vec![value(Kind::StructValue(prost_types::Struct::default()))],
+        f5: HashMap::from([("d".to_string(), prost_types::Struct::default())]),
+        f6: Some(generated::test::msg_a::F6::F7(value(Kind::NumberValue(
+            2.0,
+        )))),
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::MsgA = orig.clone().try_into().unwrap();
+    let _: &serde_json::Map<String, serde_json::Value> = &sane.f1;
+    assert_eq!(
+        serde_json::Value::Object(sane.f1.clone()),
+        json!({"a": 1.5, "b": [true, null]})
+    );
+    assert_eq!(sane.f2, Some(json!("c")));
+    assert_eq!(sane.f3, None);
+    assert_eq!(sane.f4, vec![json!({})]);
+    assert_eq!(sane.f5["d"], serde_json::Map::new());
+    assert!(matches!(
+        sane.f6,
+        Some(sane::test::msg_a::F6::F7(ref value)) if *value == json!(2.0)
+    ));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_conversion_list_value() {
+    let orig = generated::test::MsgA {
+        f3: Some(prost_types::ListValue {
+            values: vec![value(Kind::StringValue("e".to_string()))],
+        }),
+        ..original()
+    };
+    let sane: sane::test::MsgA = orig.clone().try_into().unwrap();
+    assert_eq!(sane.f3, Some(vec![json!("e")]));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_error_non_finite() {
+    let orig = generated::test::MsgA {
+        f4: vec![value(Kind::ListValue(prost_types::ListValue {
+            values: vec![value(Kind::NumberValue(f64::NAN))],
+        }))],
+        ..original()
+    };
+    let error = TryInto::<sane::test::MsgA>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "f4: number is not finite");
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::MsgA {
+        f1: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::MsgA>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "f1: field is required");
+}
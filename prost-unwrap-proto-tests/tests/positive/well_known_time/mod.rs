@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+pub mod generated {
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane_std {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/well_known_time/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::well_known_time::generated)
+        .with_this_mod(crate::positive::well_known_time::sane_std)
+        .with_timestamp(std)
+        .with_duration(std)
+        .with_struct(MsgA, [f1, f5]));
+    }
+}
+
+pub mod sane_chrono {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/well_known_time/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::well_known_time::generated)
+        .with_this_mod(crate::positive::well_known_time::sane_chrono)
+        .with_timestamp(chrono)
+        .with_duration(chrono)
+        .with_struct(MsgA, [f1, f5]));
+    }
+}
+
+pub mod sane_time {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/well_known_time/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::well_known_time::generated)
+        .with_this_mod(crate::positive::well_known_time::sane_time)
+        .with_timestamp(time)
+        .with_duration(time)
+        .with_struct(MsgA, [f1, f5]));
+    }
+}
+
+fn timestamp(seconds: i64, nanos: i32) -> prost_types::Timestamp {
+    prost_types::Timestamp { seconds, nanos }
+}
+
+fn duration(seconds: i64, nanos: i32) -> prost_types::Duration {
+    prost_types::Duration { seconds, nanos }
+}
+
+fn original() -> generated::test::MsgA {
+    generated::test::MsgA {
+        f1: Some(timestamp(1_700_000_000, 1)),
+        f2: None,
+        f3: vec![timestamp(0, 0), timestamp(-1, 999_999_999)],
+        f4: HashMap::from([("a".to_string(), timestamp(1, 0))]),
+        f5: Some(duration(1, 500)),
+        f6: Some(duration(0, 0)),
+        f7: vec![duration(3600, 0)],
+        f8: HashMap::from([("b".to_string(), duration(2, 2))]),
+        f9: Some(generated::test::msg_a::F9::F10(timestamp(10, 10))),
+    }
+}
+
+#[test]
+fn test_conversion_std() {
+    let orig = original();
+    let sane: sane_std::test::MsgA = orig.clone().try_into().unwrap();
+    assert_eq!(
+        sane.f1,
+        std::time::UNIX_EPOCH + std::time::Duration::new(1_700_000_000, 1)
+    );
+    assert_eq!(sane.f5, std::time::Duration::new(1, 500));
+    assert_eq!(sane.f7, vec![std::time::Duration::from_secs(3600)]);
+    assert!(matches!(
+        sane.f9,
+        Some(sane_std::test::msg_a::F9::F10(time)) if time == std::time::UNIX_EPOCH + std::time::Duration::new(10, 10)
+    ));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_conversion_chrono() {
+    let orig = original();
+    let sane: sane_chrono::test::MsgA = orig.clone().try_into().unwrap();
+    assert_eq!(
+        sane.f1,
+        chrono::DateTime::from_timestamp(1_700_000_000, 1).unwrap()
+    );
+    assert_eq!(
+        sane.f3[1],
+        chrono::DateTime::from_timestamp(-1, 999_999_999).unwrap()
+    );
+    assert_eq!(
+        sane.f4["a"],
+        chrono::DateTime::from_timestamp(1, 0).unwrap()
+    );
+    assert_eq!(
+        sane.f5,
+        chrono::TimeDelta::seconds(1) + chrono::TimeDelta::nanoseconds(500)
+    );
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_conversion_time() {
+    let orig = original();
+    let sane: sane_time::test::MsgA = orig.clone().try_into().unwrap();
+    assert_eq!(
+        sane.f1,
+        time::OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_001).unwrap()
+    );
+    assert_eq!(
+        sane.f3[1],
+        time::OffsetDateTime::from_unix_timestamp_nanos(-1).unwrap()
+    );
+    assert_eq!(sane.f8["b"], time::Duration::new(2, 2));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::MsgA {
+        f1: None,
+        ..original()
+    };
+    let error = TryInto::<sane_chrono::test::MsgA>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "f1: field is required");
+}
+
+#[test]
+fn test_error_out_of_range() {
+    let orig = generated::test::MsgA {
+        f3: vec![timestamp(0, 0), timestamp(0, -1)],
+        ..original()
+    };
+    let error = TryInto::<sane_std::test::MsgA>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "f3: timestamp nanos are out of range");
+
+    let orig = generated::test::MsgA {
+        f9: Some(generated::test::msg_a::F9::F11(duration(1, -1))),
+        ..original()
+    };
+    let error = TryInto::<sane_time::test::MsgA>::try_into(orig)
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "f9.F11: duration nanos are out of range");
+
+    let orig = generated::test::MsgA {
+        f5: Some(duration(-1, 0)),
+        ..original()
+    };
+    let error = TryInto::<sane_std::test::MsgA>::try_into(orig)
+        .err()
+        .unwrap();
+    assert!(error.to_string().starts_with("f5: "));
+    assert!(std::error::Error::source(&error).is_some());
+}
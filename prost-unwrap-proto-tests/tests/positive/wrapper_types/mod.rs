@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+pub mod generated {
+    pub mod google {
+        // not all of the wrapper messages are used by the test
+        #[allow(dead_code)]
+        pub mod protobuf {
+            include!(".proto_out/google.protobuf.rs");
+        }
+    }
+
+    pub mod test {
+        include!(".proto_out/test.rs");
+    }
+}
+
+pub mod sane {
+    pub mod test {
+        prost_unwrap::include!(from_source(
+            test,
+            "prost-unwrap-proto-tests/tests/positive/wrapper_types/.proto_out/test.rs"
+        )
+        .with_original_mod(crate::positive::wrapper_types::generated)
+        .with_this_mod(crate::positive::wrapper_types::sane)
+        .with_struct(MsgA, [f1, f2]));
+    }
+}
+
+use generated::google::protobuf as wrappers;
+
+fn original() -> generated::test::MsgA {
+    generated::test::MsgA {
+        f1: Some(wrappers::StringValue {
+            value: "a".to_string(),
+        }),
+        f2: Some(wrappers::Int64Value { value: -2 }),
+        f3: vec![
+            wrappers::BoolValue { value: true },
+            wrappers::BoolValue { value: false },
+        ],
+        f4: HashMap::from([(
+            "b".to_string(),
+            wrappers::BytesValue {
+                value: vec![1, 2, 3],
+            },
+        )]),
+        f5: None,
+        f6: Some(generated::test::msg_a::F6::F7(wrappers::UInt32Value {
+            value: 7,
+        })),
+        f9: None,
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let orig = original();
+    let sane: sane::test::MsgA = orig.clone().try_into().unwrap();
+    let _: &String = &sane.f1;
+    let _: &i64 = &sane.f2;
+    let _: &Option<f64> = &sane.f5;
+    assert_eq!(sane.f1, "a");
+    assert_eq!(sane.f2, -2);
+    assert_eq!(sane.f3, vec![true, false]);
+    assert_eq!(sane.f4["b"], vec![1, 2, 3]);
+    assert_eq!(sane.f5, None);
+    assert!(matches!(sane.f6, Some(sane::test::msg_a::F6::F7(7))));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_conversion_optional() {
+    let orig = generated::test::MsgA {
+        f5: Some(wrappers::DoubleValue { value: 0.5 }),
+        f6: Some(generated::test::msg_a::F6::F8(wrappers::FloatValue {
+            value: 1.5,
+        })),
+        ..original()
+    };
+    let sane: sane::test::MsgA = orig.clone().try_into().unwrap();
+    assert_eq!(sane.f5, Some(0.5));
+    assert!(matches!(sane.f6, Some(sane::test::msg_a::F6::F8(value)) if value == 1.5));
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
+
+#[test]
+fn test_error_required() {
+    let orig = generated::test::MsgA {
+        f2: None,
+        ..original()
+    };
+    let error = TryInto::<sane::test::MsgA>::try_into(orig).err().unwrap();
+    assert_eq!(error.to_string(), "f2: field is required");
+}
+
+#[test]
+fn test_conversion_wrapper_named_message() {
+    let orig = generated::test::MsgA {
+        f9: Some(generated::test::Outer {
+            f1: Some(generated::test::outer::Int64Value {
+                value: 9,
+                unit: "s".to_string(),
+            }),
+        }),
+        ..original()
+    };
+    let sane: sane::test::MsgA = orig.clone().try_into().unwrap();
+    let f1: &sane::test::outer::Int64Value = sane.f9.as_ref().unwrap().f1.as_ref().unwrap();
+    assert_eq!(f1.value, 9);
+    assert_eq!(f1.unit, "s");
+    assert_eq!(orig, Into::<generated::test::MsgA>::into(sane));
+}
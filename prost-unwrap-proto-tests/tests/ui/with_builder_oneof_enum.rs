@@ -0,0 +1,16 @@
+mod original {}
+mod test {
+    // NB: trybuild alters the cwd, so the adjustment is needed; actual cwd
+    // within trybuild test is
+    // $WORKSPACE/target/tests/trybuild/prost-unwrap-proto-tests
+    prost_unwrap::include!(from_source(
+        test,
+        "../../../../prost-unwrap-proto-tests/tests/positive/typed_builders/.proto_out/test.rs"
+    )
+    .with_original_mod(crate::original)
+    .with_this_mod(crate::test)
+    .with_oneof_enum(Content)
+    .with_builder(Content));
+}
+
+fn main() {}
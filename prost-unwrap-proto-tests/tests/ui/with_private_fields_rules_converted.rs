@@ -0,0 +1,19 @@
+mod original {}
+mod test {
+    // NB: trybuild alters the cwd, so the adjustment is needed; actual cwd
+    // within trybuild test is
+    // $WORKSPACE/target/tests/trybuild/prost-unwrap-proto-tests
+    prost_unwrap::include!(from_source(
+        test,
+        "../../../../prost-unwrap-proto-tests/tests/positive/validate_rules/.proto_out/test.rs"
+    )
+    .with_original_mod(crate::original)
+    .with_this_mod(crate::test)
+    .with_validate_rules(
+        "../../../../prost-unwrap-proto-tests/tests/positive/validate_rules/.proto/test.proto"
+    )
+    .with_newtype(Item.sku, Sku)
+    .with_private_fields(Item));
+}
+
+fn main() {}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+use std::fmt;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The error of converting an original struct into its mirror.
+///
+/// Every `include!` module re-exports this type as its `Error`, so the errors
+/// of the nested conversions are merged into a single path, even if the nested
+/// mirror comes from another `include!` module or crate.
+#[derive(Debug)]
+pub struct Error {
+    /// The field names leading to the failed field, outermost first.
+    pub path: Vec<&'static str>,
+    /// The reason of the failure.
+    pub reason: Cow<'static, str>,
+    /// The underlying error, e.g. returned by a custom converter or validator.
+    pub source: Option<BoxError>,
+}
+
+impl Error {
+    /// Creates an error with the given reason and an empty path.
+    pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            path: Vec::new(),
+            reason: reason.into(),
+            source: None,
+        }
+    }
+
+    /// Creates an error keeping the given error as the source.
+    pub fn custom<E>(source: E) -> Self
+    where
+        E: Into<BoxError>,
+    {
+        let source = source.into();
+        Self {
+            path: Vec::new(),
+            reason: source.to_string().into(),
+            source: Some(source),
+        }
+    }
+
+    /// Creates an error of the missing required field.
+    pub fn required(field: &'static str) -> Self {
+        Self::new("field is required").at(field)
+    }
+
+    /// Prefixes the path with the given field name.
+    pub fn at(mut self, field: &'static str) -> Self {
+        self.path.insert(0, field);
+        self
+    }
+
+    /// Creates an error of the failed nested conversion of the given field.
+    ///
+    /// The path of the nested `Error` is prefixed with the field name, any
+    /// other error is kept as the source.
+    pub fn nested<E>(field: &'static str, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let source: BoxError = Box::new(source);
+        match source.downcast::<Self>() {
+            Ok(error) => error.at(field),
+            Err(source) => Self::custom(source).at(field),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path.join("."))?;
+        }
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn std::error::Error + 'static))
+    }
+}
@@ -9,3 +9,9 @@
 #![doc = include_str!("../README.md")]
 
 pub use prost_unwrap_transform::include;
+
+mod error;
+mod non_empty;
+
+pub use error::Error;
+pub use non_empty::NonEmpty;
@@ -0,0 +1,73 @@
+use std::ops::Deref;
+
+/// A collection, that is guaranteed to hold at least one element.
+///
+/// The mirror struct field, specified as `field = non_empty_vec`, has the
+/// `NonEmpty<Vec<T>>` type; the emptiness is checked by the generated
+/// `TryFrom` implementation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NonEmpty<T>(T);
+
+impl<T> NonEmpty<Vec<T>> {
+    /// Wraps the vector, returns `None` if it is empty.
+    pub fn new(vec: Vec<T>) -> Option<Self> {
+        if vec.is_empty() {
+            None
+        } else {
+            Some(Self(vec))
+        }
+    }
+
+    /// Returns the first element of the vector.
+    pub fn first(&self) -> &T {
+        &self.0[0]
+    }
+
+    /// Returns the last element of the vector.
+    pub fn last(&self) -> &T {
+        &self.0[self.0.len() - 1]
+    }
+
+    /// Returns the wrapped vector.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for NonEmpty<Vec<T>> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> AsRef<[T]> for NonEmpty<Vec<T>> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> From<NonEmpty<Vec<T>>> for Vec<T> {
+    fn from(value: NonEmpty<Vec<T>>) -> Self {
+        value.0
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<Vec<T>> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonEmpty<Vec<T>> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}